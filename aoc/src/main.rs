@@ -0,0 +1,73 @@
+use clap::Parser;
+use std::path::PathBuf;
+use utils::{AocResult, OutputFormat, Registry, RunOptions};
+
+/// Runs any or all days of Advent of Code in one go
+#[derive(Debug, Parser)]
+#[command(author, about, long_about = None)]
+struct Cli {
+    /// Days to run, every solved day when empty
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    days: Vec<u8>,
+
+    /// Folder holding the `day-XX/input.txt` files
+    #[arg(short, long, default_value = ".")]
+    workspace: PathBuf,
+
+    #[command(flatten)]
+    options: RunOptions,
+}
+
+fn registry() -> Registry {
+    Registry::default()
+        .with::<day_01::Day01>()
+        .with::<day_02::Day02>()
+        .with::<day_03::Day03>()
+        .with::<day_04::Day04>()
+        .with::<day_05::Day05>()
+        .with::<day_06::Day06>()
+        .with::<day_07::Day07>()
+        .with::<day_08::Day08>()
+        .with::<day_09::Day09>()
+        .with::<day_10::Day10>()
+}
+
+fn main() -> AocResult<()> {
+    let cli = Cli::parse();
+    let registry = registry();
+    let days = if cli.days.is_empty() {
+        registry.days().collect()
+    } else {
+        cli.days.clone()
+    };
+
+    let mut failed = Vec::new();
+    for (i, day) in days.iter().copied().enumerate() {
+        if cli.options.format() == OutputFormat::Text {
+            if i > 0 {
+                println!();
+            }
+            println!("Day {day}");
+        }
+        let path = cli
+            .workspace
+            .join(format!("day-{day:0>2}"))
+            .join("input.txt");
+        let result = match registry.get(day) {
+            Some(solve) => cli.options.run_single(day, &path, |input, report| {
+                solve(input, cli.options.part(), report)
+            }),
+            None => Err(anyhow::anyhow!("day {day} isn't solved yet")),
+        };
+        if let Err(error) = result {
+            eprintln!("{error:#}");
+            failed.push(day.to_string());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("failed days: {}", failed.join(", ")))
+    }
+}
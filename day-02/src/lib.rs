@@ -0,0 +1,186 @@
+use std::ops::RangeInclusive;
+
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    ranges: Vec<RangeInclusive<u64>>,
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser, bytes::complete::tag, character::complete::u64, combinator::map,
+        multi::separated_list1, sequence::separated_pair,
+    };
+
+    let range = map(separated_pair(u64, tag("-"), u64), |(s, e)| s..=e);
+    let ranges = separated_list1(tag(","), range);
+    map(ranges, |ranges| InputData { ranges }).parse(input)
+}
+
+/// Most digits a `u64` can have
+const MAX_DIGITS: u32 = 20;
+
+/// Sum of the `digits` long numbers in `range` made of a `period` long block
+/// of digits repeated
+///
+/// Those numbers are the block times `10^(period·(k−1)) + … + 10^period + 1`,
+/// so they're found by dividing the range bounds instead of walking them.
+fn repeated_sum(range: &RangeInclusive<u64>, digits: u32, period: u32) -> u128 {
+    let multiplier: u128 = (0..digits / period).map(|i| 10u128.pow(period * i)).sum();
+    let first = u128::from(*range.start())
+        .div_ceil(multiplier)
+        .max(10u128.pow(period - 1));
+    let last = (u128::from(*range.end()) / multiplier).min(10u128.pow(period) - 1);
+    if first > last {
+        0
+    } else {
+        multiplier * (first + last) * (last - first + 1) / 2
+    }
+}
+
+/// Sum of the `digits` long numbers in `range` made of a block repeated at
+/// least twice
+///
+/// A number repeating a `p` digit block also repeats every block whose length
+/// divides `p`. Taking away the sums of those shorter periods first leaves
+/// each number counted once, with its shortest period.
+fn any_repeated_sum(range: &RangeInclusive<u64>, digits: u32) -> u128 {
+    let mut shortest: Vec<(u32, u128)> = Vec::new();
+    for period in (1..digits).filter(|&p| digits.is_multiple_of(p)) {
+        let counted: u128 = shortest
+            .iter()
+            .filter(|(p, _)| period.is_multiple_of(*p))
+            .map(|(_, sum)| sum)
+            .sum();
+        shortest.push((period, repeated_sum(range, digits, period) - counted));
+    }
+    shortest.iter().map(|(_, sum)| sum).sum()
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<u128> {
+    Ok(input
+        .ranges
+        .iter()
+        .flat_map(|r| {
+            (2..=MAX_DIGITS)
+                .step_by(2)
+                .map(move |digits| repeated_sum(r, digits, digits / 2))
+        })
+        .sum())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(input: &InputData) -> AocResult<u128> {
+    Ok(input
+        .ranges
+        .iter()
+        .flat_map(|r| (2..=MAX_DIGITS).map(move |digits| any_repeated_sum(r, digits)))
+        .sum())
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = InputData;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u128> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u128> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,\
+    222220-222224,1698522-1698528,446443-446449,38593856-38593862,\
+    565653-565659,824824821-824824827,2121212118-2121212124";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                ranges: vec![
+                    11..=22,
+                    95..=115,
+                    998..=1012,
+                    1188511880..=1188511890,
+                    222220..=222224,
+                    1698522..=1698528,
+                    446443..=446449,
+                    38593856..=38593862,
+                    565653..=565659,
+                    824824821..=824824827,
+                    2121212118..=2121212124
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 1227775554);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 4174379265);
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        // An ID counts once for every range holding it
+        assert_part!(parse, part1, "11-22,15-30", 55);
+        assert_part!(parse, part2, "11-22,15-30", 55);
+    }
+
+    #[test]
+    fn test_against_every_id() {
+        let repeats = |id: &u64, twice: bool| {
+            let s = id.to_string();
+            (1..s.len())
+                .filter(|p| !twice || p * 2 == s.len())
+                .any(|p| {
+                    s.len().is_multiple_of(p)
+                        && s.as_bytes().chunks(p).all(|c| c == &s.as_bytes()[..p])
+                })
+        };
+        for range in [
+            1..=200_000,
+            95..=115,
+            1_010..=1_010,
+            111_111..=111_111,
+            0..=9,
+        ] {
+            let input = InputData {
+                ranges: vec![range.clone()],
+            };
+            let sum = |twice| {
+                range
+                    .clone()
+                    .filter(|id| repeats(id, twice))
+                    .map(u128::from)
+                    .sum()
+            };
+            assert_eq!(part1(&input).unwrap(), sum(true), "{range:?}");
+            assert_eq!(part2(&input).unwrap(), sum(false), "{range:?}");
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use itertools::Itertools;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    batteries: Vec<String>,
+}
+
+/// Batteries turned on in a bank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    positions: Vec<usize>,
+    digits: Vec<u8>,
+}
+
+impl Selection {
+    /// Positions of the batteries in the bank, in increasing order
+    #[must_use]
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    /// Joltage of the bank, `None` when it doesn't fit in a `u128`
+    #[must_use]
+    pub fn value(&self) -> Option<u128> {
+        self.digits.iter().try_fold(0u128, |value, &d| {
+            value.checked_mul(10)?.checked_add(u128::from(d))
+        })
+    }
+}
+
+/// Picks the `k` batteries of `bank` giving the largest joltage, `None` when
+/// the bank has fewer than `k` batteries
+///
+/// It's a single pass over the bank: every battery pushes out the weaker
+/// ones picked before it, as long as enough batteries are left to fill `k`.
+#[must_use]
+pub fn select(bank: &str, k: usize) -> Option<Selection> {
+    let mut drops = bank.len().checked_sub(k)?;
+    let mut picked: Vec<(usize, u8)> = Vec::with_capacity(bank.len());
+    for (i, digit) in bank.bytes().map(|b| b - b'0').enumerate() {
+        while drops > 0 && picked.last().is_some_and(|&(_, top)| top < digit) {
+            picked.pop();
+            drops -= 1;
+        }
+        picked.push((i, digit));
+    }
+    picked.truncate(k);
+    let (positions, digits) = picked.into_iter().unzip();
+    Some(Selection { positions, digits })
+}
+
+fn selections(input: &InputData, k: usize) -> AocResult<Vec<Selection>> {
+    input
+        .batteries
+        .iter()
+        .enumerate()
+        .map(|(i, bank)| {
+            select(bank, k).with_context(|| {
+                format!(
+                    "bank {} has only {} batteries, {k} are needed",
+                    i + 1,
+                    bank.len()
+                )
+            })
+        })
+        .collect()
+}
+
+fn checked_total(selections: &[Selection]) -> Option<u128> {
+    selections
+        .iter()
+        .try_fold(0u128, |total, s| total.checked_add(s.value()?))
+}
+
+/// Adds the joltages digit by digit, for when they don't fit in a `u128`
+fn decimal_total(selections: &[Selection]) -> String {
+    let width = selections.iter().map(|s| s.digits.len()).max().unwrap_or(0);
+    // Least significant column first
+    let mut columns = vec![0u64; width];
+    for selection in selections {
+        for (column, &d) in columns.iter_mut().zip(selection.digits.iter().rev()) {
+            *column += u64::from(d);
+        }
+    }
+    let mut digits = Vec::with_capacity(width + 20);
+    let mut carry = 0;
+    for column in columns {
+        let sum = column + carry;
+        digits.push((sum % 10) as u8);
+        carry = sum / 10;
+    }
+    while carry > 0 {
+        digits.push((carry % 10) as u8);
+        carry /= 10;
+    }
+    while digits.len() > 1 && digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits.iter().rev().map(|d| char::from(b'0' + d)).collect()
+}
+
+/// Total joltage with `k` batteries on in every bank
+///
+/// It's a number while it fits in a `u128` and written out in full otherwise.
+///
+/// # Errors
+///
+/// When a bank has fewer than `k` batteries
+pub fn total_joltage(input: &InputData, k: usize) -> AocResult<Answer> {
+    let selections = selections(input, k)?;
+    Ok(checked_total(&selections).map_or_else(
+        || Answer::Text(decimal_total(&selections)),
+        Answer::Unsigned,
+    ))
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        character::complete::{digit1, line_ending},
+        combinator::map,
+        multi::separated_list1,
+    };
+
+    map(separated_list1(line_ending, digit1), |batteries| {
+        InputData {
+            batteries: batteries.into_iter().map(str::to_string).collect_vec(),
+        }
+    })
+    .parse(input)
+}
+
+fn part1(input: &InputData) -> AocResult<u128> {
+    checked_total(&selections(input, 2)?).context("the total joltage overflows")
+}
+
+fn part2(input: &InputData) -> AocResult<u128> {
+    checked_total(&selections(input, 12)?).context("the total joltage overflows")
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = InputData;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u128> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u128> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                batteries: vec![
+                    "987654321111111".to_string(),
+                    "811111111111119".to_string(),
+                    "234234234234278".to_string(),
+                    "818181911112111".to_string(),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 357);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 3121910778619);
+    }
+
+    #[test]
+    fn test_select() {
+        let selection = select("818181911112111", 12).unwrap();
+        assert_eq!(
+            selection.positions(),
+            [0, 2, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+        );
+        assert_eq!(selection.value(), Some(888911112111));
+        assert_eq!(select("234234234234278", 2).unwrap().positions(), [13, 14]);
+        assert_eq!(select("12", 3), None);
+        assert_eq!(select("12", 0).unwrap().value(), Some(0));
+        assert_eq!(select(&"9".repeat(40), 40).unwrap().value(), None);
+    }
+
+    #[test]
+    fn test_total_joltage() {
+        let (_, input) = parse(INPUT).unwrap();
+        assert_eq!(
+            total_joltage(&input, 12).unwrap(),
+            Answer::from(3121910778619u64)
+        );
+        assert!(total_joltage(&input, 16).is_err());
+
+        let input = InputData {
+            batteries: vec!["9".repeat(40), "1".repeat(40), "9".repeat(45)],
+        };
+        let expected = format!("2{}09", "1".repeat(37));
+        assert_eq!(total_joltage(&input, 39).unwrap(), Answer::Text(expected));
+    }
+}
@@ -0,0 +1,437 @@
+use std::{fmt::Display, str::FromStr};
+
+use utils::grid::{Grid, Pos, grid};
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+/// Cells around a roll that count as its neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The 4 cells sharing a side
+    Orthogonal,
+    /// The 8 cells sharing a side or a corner
+    Surrounding,
+    /// Every cell at most this many rows and columns away
+    Radius(usize),
+}
+
+impl Neighborhood {
+    /// Offsets to the neighbors, leaving out those at least `reach` rows or
+    /// columns away
+    fn offsets(self, reach: usize) -> Vec<(isize, isize)> {
+        match self {
+            Self::Orthogonal => vec![(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Self::Surrounding => Self::Radius(1).offsets(reach),
+            Self::Radius(radius) => {
+                let radius = isize::try_from(radius.min(reach)).unwrap_or(isize::MAX);
+                (-radius..=radius)
+                    .flat_map(|dr| (-radius..=radius).map(move |dc| (dr, dc)))
+                    .filter(|&offset| offset != (0, 0))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// `4`, `8` or `r` followed by the radius
+impl FromStr for Neighborhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "4" => Ok(Self::Orthogonal),
+            "8" => Ok(Self::Surrounding),
+            _ => s
+                .strip_prefix('r')
+                .and_then(|radius| radius.parse().ok())
+                .map(Self::Radius)
+                .ok_or_else(|| format!("{s:?} isn't 4, 8 or a radius like r2")),
+        }
+    }
+}
+
+impl Display for Neighborhood {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Orthogonal => write!(f, "4"),
+            Self::Surrounding => write!(f, "8"),
+            Self::Radius(radius) => write!(f, "r{radius}"),
+        }
+    }
+}
+
+/// When a forklift can reach a roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Rolls with this many neighbors or more can't be reached
+    pub threshold: usize,
+    /// Cells counted as neighbors
+    pub neighborhood: Neighborhood,
+    /// Whether the neighborhood goes around the edges of the warehouse
+    pub wrap: bool,
+}
+
+/// The rules of the puzzle: fewer than 4 of the 8 surrounding cells
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            threshold: 4,
+            neighborhood: Neighborhood::Surrounding,
+            wrap: false,
+        }
+    }
+}
+
+/// The neighbors of every cell of a warehouse under some [`Rules`]
+struct Neighbors {
+    offsets: Vec<(isize, isize)>,
+    wrap: bool,
+    width: usize,
+    height: usize,
+}
+
+impl Neighbors {
+    fn new(rules: &Rules, warehouse: &Grid<bool>) -> Self {
+        let (width, height) = (warehouse.width(), warehouse.height());
+        // Further than the warehouse is wide or high, an offset either leaves
+        // it or wraps around to a cell a shorter one already reaches
+        let mut offsets = rules.neighborhood.offsets(width.max(height));
+        if rules.wrap {
+            // Going around a small warehouse, several offsets can land on the
+            // same cell or on the roll itself
+            let wrap = |d: isize, len: usize| {
+                let len = isize::try_from(len).expect("grids fit in memory");
+                d.rem_euclid(len.max(1))
+            };
+            offsets = offsets
+                .into_iter()
+                .map(|(dr, dc)| (wrap(dr, height), wrap(dc, width)))
+                .filter(|&offset| offset != (0, 0))
+                .collect();
+            offsets.sort_unstable();
+            offsets.dedup();
+        }
+        Self {
+            offsets,
+            wrap: rules.wrap,
+            width,
+            height,
+        }
+    }
+
+    fn of(&self, (row, col): Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets.iter().filter_map(move |&(dr, dc)| {
+            if self.wrap {
+                Some((
+                    (row + dr.unsigned_abs()) % self.height,
+                    (col + dc.unsigned_abs()) % self.width,
+                ))
+            } else {
+                let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+                (pos.0 < self.height && pos.1 < self.width).then_some(pos)
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputData {
+    warehouse: Grid<bool>,
+}
+
+impl InputData {
+    fn neighbors_count(&self, neighbors: &Neighbors, pos: Pos) -> usize {
+        neighbors.of(pos).filter(|&loc| self.warehouse[loc]).count()
+    }
+
+    fn rolls(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.warehouse
+            .iter()
+            .filter_map(|(pos, &roll)| roll.then_some(pos))
+    }
+
+    /// Rolls a forklift can reach right away
+    #[must_use]
+    pub fn reachable(&self, rules: &Rules) -> Vec<Pos> {
+        let neighbors = Neighbors::new(rules, &self.warehouse);
+        self.rolls()
+            .filter(|&p| self.neighbors_count(&neighbors, p) < rules.threshold)
+            .collect()
+    }
+
+    /// Rolls taken away, wave after wave, until no roll left can be reached
+    ///
+    /// Neighbor counts are kept up to date as rolls go, so a removal only
+    /// looks at the cells around it. A roll joins the next wave the moment its
+    /// count drops below the threshold, which happens once at most.
+    #[must_use]
+    pub fn removal_waves(&self, rules: &Rules) -> Vec<Vec<Pos>> {
+        let neighbors = Neighbors::new(rules, &self.warehouse);
+        let mut warehouse = self.warehouse.clone();
+        let mut counts = Grid::new(warehouse.width(), warehouse.height(), 0);
+        for pos in self.rolls() {
+            counts[pos] = self.neighbors_count(&neighbors, pos);
+        }
+
+        let mut waves = Vec::new();
+        let mut wave = self.reachable(rules);
+        while !wave.is_empty() {
+            for &pos in &wave {
+                warehouse[pos] = false;
+            }
+            let mut next = Vec::new();
+            for &pos in &wave {
+                for neighbor in neighbors.of(pos) {
+                    if warehouse[neighbor] {
+                        counts[neighbor] -= 1;
+                        if counts[neighbor] + 1 == rules.threshold {
+                            next.push(neighbor);
+                        }
+                    }
+                }
+            }
+            next.sort_unstable();
+            waves.push(std::mem::replace(&mut wave, next));
+        }
+        waves
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{Parser, combinator::map};
+
+    let warehouse = grid(|c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    });
+    map(warehouse, |warehouse| InputData { warehouse }).parse(input)
+}
+
+/// Rolls a forklift can reach under `rules`
+#[must_use]
+pub fn part1_with(input: &InputData, rules: &Rules) -> usize {
+    input.reachable(rules).len()
+}
+
+/// Rolls the forklifts can take away under `rules`
+#[must_use]
+pub fn part2_with(input: &InputData, rules: &Rules) -> usize {
+    input.removal_waves(rules).iter().map(Vec::len).sum()
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<usize> {
+    Ok(part1_with(input, &Rules::default()))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(input: &InputData) -> AocResult<usize> {
+    Ok(part2_with(input, &Rules::default()))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                warehouse: [
+                    (0, 2),
+                    (0, 3),
+                    (0, 5),
+                    (0, 6),
+                    (0, 7),
+                    (0, 8),
+                    (1, 0),
+                    (1, 1),
+                    (1, 2),
+                    (1, 4),
+                    (1, 6),
+                    (1, 8),
+                    (1, 9),
+                    (2, 0),
+                    (2, 1),
+                    (2, 2),
+                    (2, 3),
+                    (2, 4),
+                    (2, 6),
+                    (2, 8),
+                    (2, 9),
+                    (3, 0),
+                    (3, 2),
+                    (3, 3),
+                    (3, 4),
+                    (3, 5),
+                    (3, 8),
+                    (4, 0),
+                    (4, 1),
+                    (4, 3),
+                    (4, 4),
+                    (4, 5),
+                    (4, 6),
+                    (4, 8),
+                    (4, 9),
+                    (5, 1),
+                    (5, 2),
+                    (5, 3),
+                    (5, 4),
+                    (5, 5),
+                    (5, 6),
+                    (5, 7),
+                    (5, 9),
+                    (6, 1),
+                    (6, 3),
+                    (6, 5),
+                    (6, 7),
+                    (6, 8),
+                    (6, 9),
+                    (7, 0),
+                    (7, 2),
+                    (7, 3),
+                    (7, 4),
+                    (7, 6),
+                    (7, 7),
+                    (7, 8),
+                    (7, 9),
+                    (8, 1),
+                    (8, 2),
+                    (8, 3),
+                    (8, 4),
+                    (8, 5),
+                    (8, 6),
+                    (8, 7),
+                    (8, 8),
+                    (9, 0),
+                    (9, 2),
+                    (9, 4),
+                    (9, 5),
+                    (9, 6),
+                    (9, 8),
+                ]
+                .into_iter()
+                .fold(Grid::new(10, 10, false), |mut warehouse, pos| {
+                    warehouse[pos] = true;
+                    warehouse
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 43);
+    }
+
+    #[test]
+    fn test_removal_waves() {
+        let (_, input) = parse(INPUT).unwrap();
+        let waves = input.removal_waves(&Rules::default());
+        assert_eq!(
+            waves.iter().map(Vec::len).collect::<Vec<_>>(),
+            [13, 12, 7, 5, 2, 1, 1, 1, 1]
+        );
+        assert_eq!(waves[0][..3], [(0, 2), (0, 3), (0, 5)]);
+        assert_eq!(
+            waves[1],
+            [
+                (0, 7),
+                (1, 4),
+                (1, 6),
+                (1, 9),
+                (2, 0),
+                (3, 0),
+                (3, 8),
+                (4, 8),
+                (5, 1),
+                (5, 9),
+                (6, 1),
+                (8, 1)
+            ]
+        );
+        assert_eq!(waves[8], [(3, 3)]);
+    }
+
+    #[test]
+    fn test_rules() {
+        let (_, input) = parse(INPUT).unwrap();
+        let rules = |threshold, neighborhood, wrap| Rules {
+            threshold,
+            neighborhood,
+            wrap,
+        };
+        let radius_one = rules(4, Neighborhood::Radius(1), false);
+        assert_eq!(part2_with(&input, &radius_one), 43);
+        assert_eq!(
+            part1_with(&input, &rules(0, Neighborhood::Radius(3), true)),
+            0
+        );
+        let everything = rules(100, Neighborhood::Surrounding, false);
+        assert_eq!(part1_with(&input, &everything), 71);
+
+        // In a 2 by 2 warehouse that wraps around, every cell is a neighbor
+        // of the others exactly once, however far the neighborhood reaches
+        let (_, small) = parse("@@\n@.\n").unwrap();
+        let crowded = rules(3, Neighborhood::Radius(5), true);
+        assert_eq!(small.reachable(&crowded), [(0, 0), (0, 1), (1, 0)]);
+        for wrap in [false, true] {
+            let huge = rules(20, Neighborhood::Radius(usize::MAX), wrap);
+            let clamped = rules(20, Neighborhood::Radius(10), wrap);
+            assert_eq!(input.reachable(&huge), input.reachable(&clamped));
+            assert_eq!(part2_with(&input, &huge), part2_with(&input, &clamped));
+        }
+        let wrapped = rules(2, Neighborhood::Orthogonal, true);
+        assert_eq!(
+            small.removal_waves(&wrapped),
+            [vec![(0, 1), (1, 0)], vec![(0, 0)]]
+        );
+
+        assert_eq!("r3".parse(), Ok(Neighborhood::Radius(3)));
+        assert_eq!("4".parse(), Ok(Neighborhood::Orthogonal));
+        assert!("6".parse::<Neighborhood>().is_err());
+        assert_eq!(Neighborhood::Radius(2).to_string(), "r2");
+    }
+}
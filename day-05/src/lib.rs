@@ -0,0 +1,308 @@
+use std::{
+    io::{BufRead, BufWriter, Write},
+    ops::RangeInclusive,
+};
+
+use utils::intervals::IntervalSet;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    fresh_ids: FreshIds,
+    produce: Vec<u64>,
+}
+
+impl InputData {
+    /// The fresh ranges, merged while parsing
+    #[must_use]
+    pub fn fresh_ids(&self) -> &FreshIds {
+        &self.fresh_ids
+    }
+}
+
+/// The fresh ID ranges merged into disjoint ones
+#[derive(Debug, PartialEq)]
+pub struct FreshIds {
+    ids: IntervalSet<u64>,
+    overlaps: Vec<Vec<usize>>,
+}
+
+impl FreshIds {
+    /// Merges `ranges`, noting which of them share IDs
+    #[must_use]
+    pub fn normalize(ranges: &[RangeInclusive<u64>]) -> Self {
+        let mut order: Vec<usize> = (0..ranges.len())
+            .filter(|&i| !ranges[i].is_empty())
+            .collect();
+        order.sort_by_key(|&i| ranges[i].start());
+
+        let mut overlaps = Vec::new();
+        let mut group: Vec<usize> = Vec::new();
+        let mut end = 0;
+        for i in order {
+            let (&start, &last) = (ranges[i].start(), ranges[i].end());
+            if group.is_empty() || start > end {
+                if group.len() > 1 {
+                    overlaps.push(std::mem::take(&mut group));
+                }
+                group.clear();
+                end = last;
+            }
+            group.push(i);
+            end = end.max(last);
+        }
+        if group.len() > 1 {
+            overlaps.push(group);
+        }
+        overlaps.iter_mut().for_each(|group| group.sort_unstable());
+        overlaps.sort_unstable();
+
+        Self {
+            ids: ranges.iter().cloned().collect(),
+            overlaps,
+        }
+    }
+
+    /// Whether `id` is in one of the ranges, found by binary search
+    #[must_use]
+    pub fn contains(&self, id: u64) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Groups of ranges sharing IDs, as their positions in the input
+    #[must_use]
+    pub fn overlaps(&self) -> &[Vec<usize>] {
+        &self.overlaps
+    }
+}
+
+/// A line break with more text after it, past which the list must go on
+fn line_separator(input: &str) -> ParseResult<'_, &str> {
+    use nom::{
+        Parser,
+        branch::alt,
+        character::complete::line_ending,
+        combinator::{eof, not},
+        sequence::terminated,
+    };
+
+    terminated(line_ending, not(alt((line_ending, eof)))).parse(input)
+}
+
+fn fresh_ranges(input: &str) -> ParseResult<'_, Vec<RangeInclusive<u64>>> {
+    use nom::{
+        Parser,
+        bytes::complete::tag,
+        character::complete::u64,
+        combinator::{cut, map},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    let ids = context(
+        "fresh range",
+        map(separated_pair(u64, tag("-"), u64), |(s, e)| s..=e),
+    );
+    separated_list1(line_separator, cut(ids)).parse(input)
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        character::complete::{line_ending, u64},
+        combinator::{cut, map},
+        error::context,
+        multi::{count, separated_list1},
+        sequence::separated_pair,
+    };
+
+    let produce = separated_list1(line_separator, cut(context("produce", u64)));
+    let ims = separated_pair(fresh_ranges, count(line_ending, 2), produce);
+
+    map(ims, |(fresh_ranges, produce)| InputData {
+        fresh_ids: FreshIds::normalize(&fresh_ranges),
+        produce,
+    })
+    .parse(input)
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<usize> {
+    let fresh = input.fresh_ids();
+    Ok(input.produce.iter().filter(|&&p| fresh.contains(p)).count())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(input: &InputData) -> AocResult<u128> {
+    Ok(input.fresh_ids().ids.len())
+}
+
+/// Reads the fresh ID ranges at the top of `input`, ignoring what follows
+///
+/// # Errors
+///
+/// When the input doesn't start with ranges
+pub fn load_fresh_ids(input: &str) -> AocResult<FreshIds> {
+    let ranges = finish_parse(input, fresh_ranges(input), TrailingInput::Ignore)?;
+    Ok(FreshIds::normalize(&ranges))
+}
+
+/// Writes `fresh` or `spoiled` for every line of `ids`, each holding one ID
+///
+/// The answers are buffered, so it's meant for whole streams of IDs rather
+/// than asking one at a time.
+///
+/// # Errors
+///
+/// When a line isn't an ID or reading or writing fails
+pub fn query(fresh: &FreshIds, ids: impl BufRead, out: impl Write) -> AocResult<()> {
+    let mut out = BufWriter::new(out);
+    for (i, line) in ids.lines().enumerate() {
+        let line = line.context("can't read the IDs")?;
+        let id: u64 = line
+            .trim()
+            .parse()
+            .with_context(|| format!("line {}: {line:?} isn't an ID", i + 1))?;
+        let state = if fresh.contains(id) {
+            "fresh"
+        } else {
+            "spoiled"
+        };
+        writeln!(out, "{state}")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Writes a line for every group of ranges sharing IDs, naming the lines of
+/// the input they're on
+///
+/// # Errors
+///
+/// When writing fails
+pub fn write_overlaps(fresh: &FreshIds, mut out: impl Write) -> AocResult<()> {
+    for group in fresh.overlaps() {
+        let lines: Vec<String> = group.iter().map(|i| (i + 1).to_string()).collect();
+        writeln!(out, "ranges on lines {} overlap", lines.join(", "))?;
+    }
+    Ok(())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u128> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                fresh_ids: FreshIds::normalize(&[3..=5, 10..=14, 16..=20, 12..=18]),
+                produce: vec![1, 5, 8, 11, 17, 32],
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        let error = |input| finish_parse(input, parse(input), TrailingInput::Reject).unwrap_err();
+        let diagnostic = error("3-5\n10-x\n\n1\n");
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 4));
+        assert_eq!(diagnostic.contexts().collect::<Vec<_>>(), ["fresh range"]);
+        let diagnostic = error("3-5\n\n1\n5\n-8\n");
+        assert_eq!((diagnostic.line(), diagnostic.column()), (5, 1));
+        assert_eq!(diagnostic.contexts().collect::<Vec<_>>(), ["produce"]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let fresh = FreshIds::normalize(&[3..=5, 10..=14, 16..=20, 12..=18]);
+        assert_eq!(fresh.ids, IntervalSet::from_iter([3..=5, 10..=20]));
+        assert_eq!(fresh.overlaps(), [vec![1, 2, 3]]);
+
+        // Touching ranges merge without overlapping, empty ones are dropped
+        let empty = RangeInclusive::new(7, 1);
+        let fresh = FreshIds::normalize(&[8..=9, 1..=3, 4..=6, 2..=2, empty, 9..=12]);
+        assert_eq!(fresh.ids, IntervalSet::from_iter([1..=6, 8..=12]));
+        assert_eq!(fresh.overlaps(), [vec![0, 5], vec![1, 3]]);
+        assert_eq!(fresh.ids.len(), 11);
+
+        let everything = FreshIds::normalize(&[0..=u64::MAX, 5..=5]);
+        assert_eq!(everything.ids.len(), 1 << 64);
+        assert!(everything.contains(u64::MAX));
+    }
+
+    #[test]
+    fn test_write_overlaps() {
+        let (_, input) = parse(INPUT).unwrap();
+        let mut out = Vec::new();
+        write_overlaps(input.fresh_ids(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ranges on lines 2, 3, 4 overlap\n"
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let fresh = load_fresh_ids(INPUT).unwrap();
+        let mut out = Vec::new();
+        query(&fresh, "1\n5\n8\n11\n17\n 32 \n".as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "spoiled\nfresh\nspoiled\nfresh\nfresh\nspoiled\n"
+        );
+
+        let error = query(&fresh, "3\nx\n".as_bytes(), Vec::new()).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: \"x\" isn't an ID");
+        assert!(load_fresh_ids("\n1\n").is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 14);
+    }
+}
@@ -0,0 +1,239 @@
+use itertools::Itertools;
+use utils::grid::grid;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Operation {
+    Add,
+    Multiply,
+}
+
+impl Operation {
+    fn operate<'a>(&self, numbers: impl Iterator<Item = &'a u64>) -> u64 {
+        match self {
+            Operation::Add => numbers.sum(),
+            Operation::Multiply => numbers.product(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    groups: Vec<Group>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Group {
+    numbers: Vec<u64>,
+    operation: Operation,
+}
+
+fn parse1(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{line_ending, space0, space1, u64},
+        combinator::map,
+        multi::separated_list1,
+        sequence::{delimited, separated_pair},
+    };
+    let add = map(tag("+"), |_| Operation::Add);
+    let multiply = map(tag("*"), |_| Operation::Multiply);
+    let operation = alt((add, multiply));
+    let operation_row = delimited(space0, separated_list1(space1, operation), space0);
+
+    let number_row = delimited(space0, separated_list1(space1, u64), space0);
+    let number_rows = separated_list1(line_ending, number_row);
+
+    let rows = separated_pair(number_rows, line_ending, operation_row);
+
+    map(rows, |(grid, operations)| InputData {
+        groups: operations
+            .into_iter()
+            .enumerate()
+            .map(|(i, operation)| Group {
+                numbers: grid.iter().map(|r| r[i]).collect_vec(),
+                operation,
+            })
+            .collect_vec(),
+    })
+    .parse(input)
+}
+
+fn parse2(input: &str) -> ParseResult<'_, InputData> {
+    let (rest, cells) = grid(Some)(input)?;
+    let transposed = cells
+        .transpose()
+        .rows()
+        .rev()
+        .map(|column| column.iter().collect::<String>())
+        .join("\n");
+
+    fn inner(input: &str) -> ParseResult<'_, InputData> {
+        use nom::{
+            Parser,
+            branch::alt,
+            bytes::complete::tag,
+            character::complete::{line_ending, space0, space1, u64},
+            combinator::map,
+            multi::separated_list1,
+            sequence::{delimited, pair},
+        };
+
+        let add = map(tag("+"), |_| Operation::Add);
+        let multiply = map(tag("*"), |_| Operation::Multiply);
+        let operation = alt((add, multiply));
+
+        let empty_row = delimited(line_ending, space1, line_ending);
+
+        let number_row = delimited(space0, u64, space0);
+        let number_rows = separated_list1(line_ending, number_row);
+
+        let group = map(pair(number_rows, operation), |(numbers, operation)| Group {
+            numbers,
+            operation,
+        });
+
+        map(separated_list1(empty_row, group), |groups| InputData {
+            groups,
+        })
+        .parse(input)
+    }
+    let data = finish_parse(&transposed, inner(&transposed), TrailingInput::Reject).map_err(
+        |diagnostic| {
+            // Line `l` of the transposed text is column `width - l` of the
+            // input, read top to bottom
+            let row = diagnostic.column() - 1;
+            let column = cells.width().saturating_sub(diagnostic.line());
+            let offset = input
+                .split_inclusive('\n')
+                .take(row)
+                .map(str::len)
+                .sum::<usize>()
+                + column;
+            nom::Err::Error(ParseError::custom(
+                input.get(offset..).unwrap_or(input),
+                "expected a column of numbers ending with an operation",
+            ))
+        },
+    )?;
+    Ok((rest, data))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn operate(input: &InputData) -> AocResult<u64> {
+    Ok(input
+        .groups
+        .iter()
+        .map(|Group { numbers, operation }| operation.operate(numbers.iter()))
+        .sum())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const PARSE_PART2: Option<ParseFn<InputData>> = Some(parse2);
+
+    type Input = InputData;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse1(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<u64> {
+        operate(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u64> {
+        operate(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  
+";
+
+    #[test]
+    fn test_parser1() {
+        assert_parser!(
+            parse1,
+            INPUT,
+            InputData {
+                groups: vec![
+                    Group {
+                        numbers: vec![123, 45, 6],
+                        operation: Operation::Multiply
+                    },
+                    Group {
+                        numbers: vec![328, 64, 98],
+                        operation: Operation::Add
+                    },
+                    Group {
+                        numbers: vec![51, 387, 215],
+                        operation: Operation::Multiply
+                    },
+                    Group {
+                        numbers: vec![64, 23, 314],
+                        operation: Operation::Add
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parser2() {
+        assert_parser!(
+            parse2,
+            INPUT,
+            InputData {
+                groups: vec![
+                    Group {
+                        numbers: vec![4, 431, 623],
+                        operation: Operation::Add
+                    },
+                    Group {
+                        numbers: vec![175, 581, 32],
+                        operation: Operation::Multiply
+                    },
+                    Group {
+                        numbers: vec![8, 248, 369],
+                        operation: Operation::Add
+                    },
+                    Group {
+                        numbers: vec![356, 24, 1],
+                        operation: Operation::Multiply
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse1, operate, INPUT, 4277556);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse2, operate, INPUT, 3263827);
+    }
+
+    #[test]
+    fn test_parser2_errors() {
+        let input = INPUT.replace("+  \n", "-  \n");
+        let diagnostic = finish_parse(&input, parse2(&input), TrailingInput::Reject).unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (4, 13));
+    }
+}
@@ -0,0 +1,286 @@
+use clap::ValueEnum;
+use utils::grid::{Grid, grid};
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+/// Where a beam split off past the side of the manifold goes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Boundary {
+    /// It leaves the manifold and is gone
+    #[default]
+    Drop,
+    /// It stays in the column at the edge
+    Clamp,
+    /// It comes back in on the other side
+    Wrap,
+}
+
+impl Boundary {
+    /// Columns the beams split at `col` continue in
+    fn sides(self, col: usize, width: usize) -> impl Iterator<Item = usize> {
+        let left = col.checked_sub(1);
+        let right = Some(col + 1).filter(|&c| c < width);
+        let (left, right) = match self {
+            Self::Drop => (left, right),
+            Self::Clamp => (left.or(Some(0)), right.or(Some(width - 1))),
+            Self::Wrap => (left.or(Some(width - 1)), right.or(Some(0))),
+        };
+        left.into_iter().chain(right)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    start: usize,
+    rows: Grid<bool>,
+}
+
+impl InputData {
+    /// Sends a beam down the manifold from the start, returning what reaches
+    /// every column of the bottom
+    ///
+    /// Beams meeting in a column are combined with `merge` and `on_split`
+    /// sees every beam reaching a splitter.
+    fn run<T: Copy + Default>(
+        &self,
+        boundary: Boundary,
+        beam: T,
+        merge: impl Fn(T, T) -> T,
+        mut on_split: impl FnMut(T),
+    ) -> Vec<T> {
+        let width = self.rows.width();
+        let mut beams = vec![T::default(); width];
+        beams[self.start] = beam;
+        for row in self.rows.rows() {
+            let mut next = vec![T::default(); width];
+            for (col, (&splitter, &beam)) in row.iter().zip(&beams).enumerate() {
+                if splitter {
+                    on_split(beam);
+                    for side in boundary.sides(col, width) {
+                        next[side] = merge(next[side], beam);
+                    }
+                } else {
+                    next[col] = merge(next[col], beam);
+                }
+            }
+            beams = next;
+        }
+        beams
+    }
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        character::complete::{line_ending, not_line_ending},
+        combinator::map_opt,
+        error::context,
+        sequence::terminated,
+    };
+
+    let start = map_opt(terminated(not_line_ending, line_ending), |line: &str| {
+        Some((line.find('S')?, line.chars().count()))
+    });
+    let (manifold, (start, width)) = context("start", start).parse(input)?;
+    let (rest, rows) = grid(|c| match c {
+        '^' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })(manifold)?;
+    if rows.width() != width {
+        return Err(nom::Err::Error(ParseError::custom(
+            manifold,
+            "manifold rows must be as wide as the start row",
+        )));
+    }
+    Ok((rest, InputData { start, rows }))
+}
+
+/// Times the beam is split on its way down, with beams leaving on the sides
+/// handled as `boundary` says
+#[must_use]
+pub fn part1_with(input: &InputData, boundary: Boundary) -> usize {
+    let mut splits = 0;
+    input.run(
+        boundary,
+        true,
+        |a, b| a | b,
+        |beam| splits += usize::from(beam),
+    );
+    splits
+}
+
+/// Paths a single tachyon can take down the manifold, with beams leaving on
+/// the sides handled as `boundary` says
+///
+/// Dropped beams never reach the bottom, so their paths aren't counted.
+#[must_use]
+pub fn part2_with(input: &InputData, boundary: Boundary) -> usize {
+    input
+        .run(boundary, 1, |a, b| a + b, |_| ())
+        .into_iter()
+        .sum()
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<usize> {
+    Ok(part1_with(input, Boundary::default()))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part2(input: &InputData) -> AocResult<usize> {
+    Ok(part2_with(input, Boundary::default()))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                start: 7,
+                rows: Grid::from_rows(vec![
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, true, false, false, false,
+                        false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, true, false, true, false, false,
+                        false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, true, false, true, false, true, false,
+                        false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, true, false, true, false, false, false, true,
+                        false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, false, true, false, true, false, false, false, true, false,
+                        true, false, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, false, true, false, false, false, true, false, false, false, false,
+                        false, true, false, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ],
+                    vec![
+                        false, true, false, true, false, true, false, true, false, true, false,
+                        false, false, true, false
+                    ],
+                    vec![
+                        false, false, false, false, false, false, false, false, false, false,
+                        false, false, false, false, false
+                    ]
+                ])
+                .unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 40);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let error = |input| finish_parse(input, parse(input), TrailingInput::Reject).unwrap_err();
+        assert_eq!(error("..S..\n.....\n..^.\n").line(), 3);
+        let diagnostic = error("..S..\n...\n.^.\n");
+        assert_eq!(diagnostic.line(), 2);
+        assert!(
+            diagnostic
+                .to_string()
+                .contains("manifold rows must be as wide as the start row")
+        );
+    }
+
+    #[test]
+    fn test_boundaries() {
+        // The beam split at the left edge goes off the side, only wrapping
+        // brings it to the splitter on the right edge
+        let (_, input) = parse(".S..\n^...\n.^..\n^...\n...^\n").unwrap();
+        let answers = |boundary| (part1_with(&input, boundary), part2_with(&input, boundary));
+        assert_eq!(answers(Boundary::Drop), (2, 2));
+        assert_eq!(answers(Boundary::Clamp), (2, 3));
+        assert_eq!(answers(Boundary::Wrap), (3, 4));
+    }
+}
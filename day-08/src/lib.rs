@@ -0,0 +1,211 @@
+use itertools::Itertools;
+use std::collections::BinaryHeap;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+use utils::{
+    dsu::DisjointSet,
+    geom::{Point3, point},
+};
+
+type Point = Point3<u64>;
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    points: Vec<Point>,
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        branch::alt,
+        character::complete::line_ending,
+        combinator::{cut, eof, map, not},
+        error::context,
+        multi::separated_list1,
+        sequence::terminated,
+    };
+
+    // Past a line break with more text after it, a junction box must follow
+    let separator = terminated(line_ending, not(alt((line_ending, eof))));
+    let points = separated_list1(separator, cut(context("junction box", point(","))));
+    map(points, |points| InputData { points }).parse(input)
+}
+
+/// A connection between two junction boxes as `(square distance, a, b)`
+type Pair = (u64, usize, usize);
+
+/// The `count` pairs of junction boxes closest to each other, closest first
+fn closest_pairs(points: &[Point], count: usize) -> Vec<Pair> {
+    let mut closest = BinaryHeap::with_capacity(count + 1);
+    for (a, pa) in points.iter().enumerate() {
+        for (b, pb) in points.iter().enumerate().skip(a + 1) {
+            let pair = (pa.square_distance(pb), a, b);
+            if closest.len() < count {
+                closest.push(pair);
+            } else if let Some(mut farthest) = closest.peek_mut()
+                && pair < *farthest
+            {
+                *farthest = pair;
+            }
+        }
+    }
+    closest.into_sorted_vec()
+}
+
+/// Connections of a minimum spanning tree over all junction boxes
+///
+/// Prim's algorithm on the implicit complete graph, so memory stays linear in
+/// the number of boxes.
+fn spanning_tree(points: &[Point]) -> Vec<Pair> {
+    let mut closest: Vec<Option<(u64, usize)>> = vec![Some((u64::MAX, 0)); points.len()];
+    let mut edges = Vec::with_capacity(points.len().saturating_sub(1));
+    let mut current = 0;
+    if let Some(first) = closest.first_mut() {
+        *first = None;
+    }
+    for _ in 1..points.len() {
+        let mut next: Option<(u64, usize, usize)> = None;
+        for (b, best) in closest.iter_mut().enumerate() {
+            let Some(best) = best else {
+                continue;
+            };
+            let distance = points[current].square_distance(&points[b]);
+            if distance < best.0 {
+                *best = (distance, current);
+            }
+            if next.is_none_or(|(d, _, _)| best.0 < d) {
+                next = Some((best.0, best.1, b));
+            }
+        }
+        let (distance, a, b) = next.expect("a box is left outside the tree");
+        closest[b] = None;
+        edges.push((distance, a, b));
+        current = b;
+    }
+    edges
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn part1(input: &InputData) -> AocResult<usize> {
+    let connections = if input.points.len() == 20 { 10 } else { 1000 };
+    let mut circuits = DisjointSet::new(input.points.len());
+    for (_, a, b) in closest_pairs(&input.points, connections) {
+        circuits.union(a, b);
+    }
+    Ok(circuits
+        .component_sizes()
+        .sorted_unstable_by(|a, b| b.cmp(a))
+        .take(3)
+        .product())
+}
+
+fn part2(input: &InputData) -> AocResult<u64> {
+    let mut circuits = DisjointSet::new(input.points.len());
+    spanning_tree(&input.points)
+        .into_iter()
+        .sorted_unstable()
+        .find_map(|(_, a, b)| {
+            circuits.union(a, b);
+            (circuits.component_count() == 1).then(|| input.points[a].x() * input.points[b].x())
+        })
+        .context("the junction boxes never form a single circuit")
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<u64> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                points: vec![
+                    Point3::from([162, 817, 812]),
+                    Point3::from([57, 618, 57]),
+                    Point3::from([906, 360, 560]),
+                    Point3::from([592, 479, 940]),
+                    Point3::from([352, 342, 300]),
+                    Point3::from([466, 668, 158]),
+                    Point3::from([542, 29, 236]),
+                    Point3::from([431, 825, 988]),
+                    Point3::from([739, 650, 466]),
+                    Point3::from([52, 470, 668]),
+                    Point3::from([216, 146, 977]),
+                    Point3::from([819, 987, 18]),
+                    Point3::from([117, 168, 530]),
+                    Point3::from([805, 96, 715]),
+                    Point3::from([346, 949, 466]),
+                    Point3::from([970, 615, 88]),
+                    Point3::from([941, 993, 340]),
+                    Point3::from([862, 61, 35]),
+                    Point3::from([984, 92, 344]),
+                    Point3::from([425, 690, 689])
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 40);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 25272);
+    }
+
+    #[test]
+    fn test_malformed_box() {
+        let source = "162,817,812\n57,618;57\n906,360,560\n";
+        let diagnostic = finish_parse(source, parse(source), TrailingInput::Reject).unwrap_err();
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 7));
+        assert_eq!(diagnostic.contexts().collect::<Vec<_>>(), ["junction box"]);
+    }
+}
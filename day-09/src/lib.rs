@@ -0,0 +1,151 @@
+use geo::{Contains, LineString, Polygon, Rect, coord};
+use itertools::Itertools;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use utils::geom::{Bounds, Point2, point};
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+
+type Point = Point2<usize>;
+type Rectangle = Bounds<usize, 2>;
+
+fn rect(rectangle: &Rectangle) -> Rect<f64> {
+    let (a, b) = (rectangle.min(), rectangle.max());
+    Rect::new(
+        coord! { x: a.x() as f64, y: a.y() as f64 },
+        coord! { x: b.x() as f64, y: b.y() as f64 },
+    )
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    points: Vec<Point>,
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{Parser, character::complete::line_ending, combinator::map, multi::separated_list1};
+
+    map(separated_list1(line_ending, point(",")), |points| {
+        InputData { points }
+    })
+    .parse(input)
+}
+
+fn area(rectangle: &Rectangle) -> AocResult<usize> {
+    rectangle.area().with_context(|| {
+        format!(
+            "the area between {} and {} overflows",
+            rectangle.min(),
+            rectangle.max()
+        )
+    })
+}
+
+fn part1(input: &InputData) -> AocResult<usize> {
+    input
+        .points
+        .iter()
+        .copied()
+        .tuple_combinations()
+        .par_bridge()
+        .map(|(a, b)| area(&Rectangle::new(a, b)))
+        .try_reduce(|| 0, |a, b| Ok(a.max(b)))
+}
+
+fn part2(input: &InputData) -> AocResult<usize> {
+    let tiles = Polygon::new(
+        LineString::from(
+            input
+                .points
+                .iter()
+                .map(|p| (p.x() as f64, p.y() as f64))
+                .collect_vec(),
+        ),
+        vec![],
+    );
+
+    input
+        .points
+        .iter()
+        .copied()
+        .tuple_combinations()
+        .par_bridge()
+        .map(|(a, b)| Rectangle::new(a, b))
+        .filter(|r| tiles.contains(&rect(r)))
+        .map(|r| area(&r))
+        .try_reduce(|| 0, |a, b| Ok(a.max(b)))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                points: vec![
+                    Point2::from([7, 1]),
+                    Point2::from([11, 1]),
+                    Point2::from([11, 7]),
+                    Point2::from([9, 7]),
+                    Point2::from([9, 5]),
+                    Point2::from([2, 5]),
+                    Point2::from([2, 3]),
+                    Point2::from([7, 3])
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 50);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = InputData {
+            points: vec![geom::Point([0, 0]), geom::Point([usize::MAX / 2, 2])],
+        };
+        assert!(part1(&input).is_err());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 24);
+    }
+}
@@ -0,0 +1,290 @@
+use itertools::Itertools;
+use rayon::prelude::*;
+#[allow(clippy::wildcard_imports)]
+use utils::*;
+use utils::{bitset::BitSet, counters::Counters, linear};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct LightPanel {
+    len: usize,
+    on: BitSet,
+}
+
+/// Joltage counters of a machine
+///
+/// There are at most 64 of them, as many as a button can wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct BatteryBank {
+    batteries: Counters<{ BitSet::<1>::CAPACITY }>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Machine {
+    lights: LightPanel,
+    buttons: Vec<BitSet>,
+    batteries: BatteryBank,
+}
+
+impl Machine {
+    /// Buttons to press once each to light the panel, as few as possible
+    fn start_sequence(&self) -> AocResult<Vec<usize>> {
+        if self.buttons.len() > 64 {
+            bail!("machines with more than 64 buttons aren't supported");
+        }
+        let buttons = self.buttons.iter().map(BitSet::bits).collect_vec();
+        let presses = linear::min_weight_gf2(&buttons, self.lights.on.bits())
+            .context("no button sequence lights the panel")?;
+        Ok((0..buttons.len())
+            .filter(|&button| presses >> button & 1 == 1)
+            .collect())
+    }
+
+    /// Presses of every button that bring the counters to their joltage
+    /// levels with as few presses as possible, and their total
+    fn set_joltage(&self) -> AocResult<(Vec<usize>, usize)> {
+        let levels = &self.batteries.batteries;
+        let matrix = (0..levels.len())
+            .map(|counter| {
+                self.buttons
+                    .iter()
+                    .map(|button| i64::from(button.contains(counter)))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let target = levels.iter().map(|&level| i64::from(level)).collect_vec();
+        // A button can't be pressed more often than the lowest level it raises
+        let upper = self
+            .buttons
+            .iter()
+            .map(|button| button.iter().map(|c| target[c]).min().unwrap_or(0))
+            .collect_vec();
+
+        let presses = linear::min_sum_solution(&matrix, &target, &upper)
+            .context("no button presses reach the joltage levels")?
+            .into_iter()
+            .map(|p| usize::try_from(p).expect("presses are never negative"))
+            .collect_vec();
+        let total = presses.iter().sum();
+        Ok((presses, total))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InputData {
+    machines: Vec<Machine>,
+}
+
+fn parse(input: &str) -> ParseResult<'_, InputData> {
+    use nom::{
+        Parser,
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{line_ending, space1, u32, usize},
+        combinator::{map, map_opt, value, verify},
+        error::context,
+        multi::{many1, separated_list1},
+        sequence::delimited,
+    };
+
+    let light = alt((value(false, tag(".")), value(true, tag("#"))));
+    let lights = map(
+        verify(
+            delimited(tag("["), many1(light), tag("]")),
+            |lights: &Vec<bool>| lights.len() <= BitSet::<1>::CAPACITY,
+        ),
+        |lights| LightPanel {
+            len: lights.len(),
+            on: lights.iter().positions(|&on| on).collect(),
+        },
+    );
+
+    let index = verify(usize, |&i| i < BitSet::<1>::CAPACITY);
+    let button = delimited(tag("("), separated_list1(tag(","), index), tag(")"));
+    let buttons = separated_list1(space1, map(button, BitSet::from_iter));
+
+    let batteries = map_opt(
+        delimited(tag("{"), separated_list1(tag(","), u32), tag("}")),
+        |batteries: Vec<u32>| {
+            Some(BatteryBank {
+                batteries: Counters::try_from(batteries.as_slice()).ok()?,
+            })
+        },
+    );
+
+    let mut fields = (
+        context("light panel", lights),
+        space1,
+        context("buttons", buttons),
+        space1,
+        context("joltage levels", batteries),
+    );
+    let machine = move |input| {
+        let (rest, (lights, _, buttons, _, batteries)) = fields.parse(input)?;
+        let counters = lights.len.min(batteries.batteries.len());
+        if buttons.iter().flat_map(BitSet::iter).any(|i| i >= counters) {
+            return Err(nom::Err::Error(ParseError::custom(
+                input,
+                "a button wires a light or counter the machine doesn't have",
+            )));
+        }
+        Ok((
+            rest,
+            Machine {
+                lights,
+                buttons,
+                batteries,
+            },
+        ))
+    };
+    map(separated_list1(line_ending, machine), |machines| {
+        InputData { machines }
+    })
+    .parse(input)
+}
+
+fn part1(input: &InputData) -> AocResult<usize> {
+    input
+        .machines
+        .iter()
+        .map(|m| m.start_sequence().map(|presses| presses.len()))
+        .sum()
+}
+
+fn part2(input: &InputData) -> AocResult<usize> {
+    let progress = Progress::new("machines", input.machines.len());
+    input
+        .machines
+        .par_iter()
+        .map(|m| {
+            let presses = m.set_joltage().map(|(_, total)| total);
+            progress.tick();
+            presses
+        })
+        .sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = InputData;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {
+        parse(input)
+    }
+
+    fn part1(input: &InputData) -> AocResult<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &InputData) -> AocResult<usize> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    fn set(values: &[usize]) -> BitSet {
+        values.iter().copied().collect()
+    }
+
+    fn machine(lights: &str, buttons: &[&[usize]], batteries: &[u32]) -> Machine {
+        Machine {
+            lights: LightPanel {
+                len: lights.len(),
+                on: lights.chars().positions(|c| c == '#').collect(),
+            },
+            buttons: buttons.iter().map(|button| set(button)).collect(),
+            batteries: BatteryBank {
+                batteries: Counters::try_from(batteries).unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_parser!(
+            parse,
+            INPUT,
+            InputData {
+                machines: vec![
+                    machine(
+                        ".##.",
+                        &[&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]],
+                        &[3, 5, 4, 7]
+                    ),
+                    machine(
+                        "...#.",
+                        &[&[0, 2, 3, 4], &[2, 3], &[0, 4], &[0, 1, 2], &[1, 2, 3, 4]],
+                        &[7, 5, 12, 7, 2]
+                    ),
+                    machine(
+                        ".###.#",
+                        &[&[0, 1, 2, 3, 4], &[0, 3, 4], &[0, 1, 2, 4, 5], &[1, 2]],
+                        &[10, 11, 11, 5, 10, 5]
+                    ),
+                ]
+            }
+        );
+        let many_lights = format!("[{}] (0) {{1}}", ".".repeat(65));
+        assert!(parse(&many_lights).is_err());
+        assert!(parse("[.#] (0,64) {1,1}").is_err());
+        assert!(parse(&format!("[.#] (0) {{{}}}", ["1"; 65].join(","))).is_err());
+        let many_counters = format!("[.#] (0) (1) {{{}}}", ["0"; 40].join(","));
+        assert!(parse(&many_counters).is_ok());
+        assert!(parse("[.#] (1,5) (0) {1,1}").is_err());
+        assert!(parse("[.#.] (2) {1,1}").is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_part!(parse, part1, INPUT, 7);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_part!(parse, part2, INPUT, 33);
+    }
+
+    #[test]
+    fn test_start_sequence() {
+        let (_, input) = parse(INPUT).unwrap();
+        for (machine, expected) in input.machines.iter().zip([2, 3, 2]) {
+            let presses = machine.start_sequence().unwrap();
+            assert_eq!(presses.len(), expected);
+            let lights = presses.iter().fold(BitSet::new(), |lights, &button| {
+                lights ^ machine.buttons[button]
+            });
+            assert_eq!(lights, machine.lights.on);
+        }
+    }
+
+    #[test]
+    fn test_joltage_presses() {
+        let (_, input) = parse(INPUT).unwrap();
+        for (machine, expected) in input.machines.iter().zip([10, 12, 11]) {
+            let (presses, total) = machine.set_joltage().unwrap();
+            assert_eq!(total, expected);
+            for (counter, &level) in machine.batteries.batteries.iter().enumerate() {
+                let raised: usize = machine
+                    .buttons
+                    .iter()
+                    .zip(&presses)
+                    .filter(|(button, _)| button.contains(counter))
+                    .map(|(_, presses)| presses)
+                    .sum();
+                assert_eq!(raised, level as usize);
+            }
+        }
+    }
+}
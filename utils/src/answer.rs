@@ -0,0 +1,243 @@
+use serde::{Serialize, Serializer};
+use std::{convert::Infallible, fmt::Display, path::Path, str::FromStr};
+
+use crate::{AocResult, SolutionPart};
+
+/// The answer to a part, whatever type the part computed it in
+///
+/// Whole numbers are compared by value, so a part returning `usize` matches
+/// an expected `u64` or `i64` with the same value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Zero and positive whole numbers
+    Unsigned(u128),
+    /// Negative whole numbers
+    Signed(i128),
+    /// Anything that isn't a number
+    Text(String),
+    /// The part hasn't been solved yet
+    Unsolved,
+}
+
+impl Answer {
+    /// Converts `expected` to an answer, taking its type from `part` when
+    /// `expected` is an untyped literal
+    ///
+    /// This is what lets tests write `4174379265` instead of `4174379265u64`.
+    pub fn expected<T, E: ExpectedAnswer<T>>(_part: &T, expected: E) -> Self {
+        expected.into_answer()
+    }
+}
+
+/// Values that can be compared against the answer of a part returning `T`
+pub trait ExpectedAnswer<T> {
+    fn into_answer(self) -> Answer;
+}
+
+impl<T: Into<Answer>> ExpectedAnswer<T> for T {
+    fn into_answer(self) -> Answer {
+        self.into()
+    }
+}
+
+impl ExpectedAnswer<String> for &str {
+    fn into_answer(self) -> Answer {
+        self.into()
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Unsigned(value as u128)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    let value = value as i128;
+                    u128::try_from(value).map_or(Self::Signed(value), Self::Unsigned)
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<()> for Answer {
+    fn from((): ()) -> Self {
+        Self::Unsolved
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned(n) => write!(f, "{n}"),
+            Self::Signed(n) => write!(f, "{n}"),
+            Self::Text(s) => write!(f, "{s}"),
+            Self::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Whole numbers become numeric answers, anything else is text
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(s.parse::<u128>()
+            .map(Self::Unsigned)
+            .or_else(|_| s.parse::<i128>().map(Self::Signed))
+            .unwrap_or_else(|_| Self::Text(s.to_string())))
+    }
+}
+
+/// Numbers too big for a JSON number are written as strings
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unsigned(n) => match u64::try_from(*n) {
+                Ok(n) => serializer.serialize_u64(n),
+                Err(_) => serializer.collect_str(n),
+            },
+            Self::Signed(n) => match i64::try_from(*n) {
+                Ok(n) => serializer.serialize_i64(n),
+                Err(_) => serializer.collect_str(n),
+            },
+            Self::Text(s) => serializer.serialize_str(s),
+            Self::Unsolved => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Known correct answers for a day's real input
+///
+/// Stored as `answers.toml` next to `input.txt`:
+///
+/// ```toml
+/// part1 = 1227775554
+/// part2 = "4174379265"
+/// ```
+///
+/// Numbers that don't fit a TOML integer can be written as strings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AnswerKey {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl AnswerKey {
+    /// Reads the answer key from a TOML file
+    ///
+    /// # Errors
+    ///
+    /// When the file can't be read or isn't valid TOML
+    pub fn load(path: &Path) -> AocResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("can't read {}: {e}", path.display()))?;
+        content
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid {}: {e}", path.display()))
+    }
+
+    /// Expected answer of a part, if it is known
+    #[must_use]
+    pub fn get(&self, part: SolutionPart) -> Option<&Answer> {
+        match part {
+            SolutionPart::PartOne => self.part1.as_ref(),
+            SolutionPart::PartTwo => self.part2.as_ref(),
+            SolutionPart::Both => None,
+        }
+    }
+}
+
+impl FromStr for AnswerKey {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let answer = |key: &str| match table.get(key)? {
+            toml::Value::Integer(n) => Some(Answer::from(*n)),
+            toml::Value::String(s) => s.parse().ok(),
+            value => Some(Answer::Text(value.to_string())),
+        };
+        Ok(Self {
+            part1: answer("part1"),
+            part2: answer("part2"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_by_value() {
+        assert_eq!(Answer::from(14usize), Answer::from(14u64));
+        assert_eq!(Answer::from(14i64), Answer::from(14u8));
+        assert_eq!(Answer::from(-3i32), Answer::Signed(-3));
+        assert_ne!(Answer::from(14u64), Answer::from("14"));
+    }
+
+    #[test]
+    fn test_expected() {
+        assert_eq!(
+            Answer::expected(&4174379265u64, 4174379265),
+            Answer::Unsigned(4174379265)
+        );
+        assert_eq!(
+            Answer::expected(&String::from("abc"), "abc"),
+            Answer::from("abc")
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(serde_json::to_string(&Answer::from(42u8)).unwrap(), "42");
+        assert_eq!(serde_json::to_string(&Answer::from(-42i64)).unwrap(), "-42");
+        assert_eq!(
+            serde_json::to_string(&Answer::from(u128::MAX)).unwrap(),
+            format!("\"{}\"", u128::MAX)
+        );
+        assert_eq!(serde_json::to_string(&Answer::from(())).unwrap(), "null");
+    }
+
+    #[test]
+    fn test_answer_key() {
+        let key: AnswerKey = "part1 = 357\npart2 = \"3121910778619\"\n".parse().unwrap();
+        assert_eq!(key.get(SolutionPart::PartOne), Some(&Answer::from(357u64)));
+        assert_eq!(
+            key.get(SolutionPart::PartTwo),
+            Some(&Answer::from(3121910778619u64))
+        );
+
+        let key: AnswerKey = "part1 = \"EFHL\"\n".parse().unwrap();
+        assert_eq!(key.get(SolutionPart::PartOne), Some(&Answer::from("EFHL")));
+        assert_eq!(key.get(SolutionPart::PartTwo), None);
+    }
+}
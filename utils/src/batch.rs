@@ -0,0 +1,153 @@
+use anyhow::Context;
+use serde_json::json;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{AocResult, OutputFormat, Report, SolutionPart};
+
+/// Lists the inputs to run, directories are replaced by the files they contain
+///
+/// Hidden files and `.toml` files (answer keys) inside directories are skipped.
+///
+/// # Errors
+///
+/// When a directory can't be listed
+pub fn expand_inputs(paths: &[PathBuf]) -> AocResult<Vec<PathBuf>> {
+    let mut inputs = Vec::with_capacity(paths.len());
+    for path in paths {
+        if path.is_dir() {
+            let mut files = std::fs::read_dir(path)
+                .and_then(|dir| {
+                    dir.map(|entry| entry.map(|e| e.path()))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .with_context(|| format!("can't list {}", path.display()))?;
+            files.retain(|file| is_input(file));
+            files.sort();
+            inputs.append(&mut files);
+        } else {
+            inputs.push(path.clone());
+        }
+    }
+    Ok(inputs)
+}
+
+fn is_input(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'));
+    let toml = path.extension().is_some_and(|ext| ext == "toml");
+    path.is_file() && !hidden && !toml
+}
+
+/// Reports of a day run on several inputs, printed as one summary
+#[derive(Debug, Default)]
+pub struct Batch {
+    runs: Vec<(Report, Option<anyhow::Error>)>,
+}
+
+impl Batch {
+    /// Adds the report of one input and how the run ended
+    pub fn push(&mut self, report: Report, result: AocResult<()>) {
+        self.runs.push((report, result.err()));
+    }
+
+    /// Prints the summary table, or one JSON object per input
+    ///
+    /// # Errors
+    ///
+    /// When any of the inputs failed
+    pub fn finish(&self, format: OutputFormat) -> AocResult<()> {
+        match format {
+            OutputFormat::Text => print!("{self}"),
+            OutputFormat::Json => {
+                for (report, error) in &self.runs {
+                    let mut json = report.to_json();
+                    if let Some(error) = error {
+                        json["error"] = json!(format!("{error:#}"));
+                    }
+                    println!("{json}");
+                }
+            }
+        }
+        let failed = self.runs.iter().filter(|(_, e)| e.is_some()).count();
+        if failed == 0 {
+            Ok(())
+        } else {
+            if format == OutputFormat::Text {
+                for (report, error) in &self.runs {
+                    if let Some(error) = error {
+                        eprintln!("\n{}:\n{error:#}", report.input().unwrap_or_default());
+                    }
+                }
+            }
+            anyhow::bail!("{failed} of {} inputs failed", self.runs.len())
+        }
+    }
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rows = vec![[
+            "Input".to_string(),
+            "Part 1".to_string(),
+            "Part 2".to_string(),
+            "Time".to_string(),
+        ]];
+        rows.extend(self.runs.iter().map(|(report, error)| {
+            let answer = |part| match (error, report.answer_of(part)) {
+                (_, Some(answer)) => answer.to_string(),
+                (Some(_), None) => "error".to_string(),
+                (None, None) => "-".to_string(),
+            };
+            [
+                report.input().unwrap_or_default().to_string(),
+                answer(SolutionPart::PartOne),
+                answer(SolutionPart::PartTwo),
+                format!("{:.3?}", report.timings().total()),
+            ]
+        }));
+        let widths: Vec<usize> = (0..4)
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row[i].len())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        for [input, part1, part2, time] in &rows {
+            writeln!(
+                f,
+                "{input:<w0$}  {part1:>w1$}  {part2:>w2$}  {time:>w3$}",
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-utils-{}-batch", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["b.txt", "a.txt.gz", "answers.toml", ".hidden"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        let stdin = PathBuf::from("-");
+        assert_eq!(
+            expand_inputs(&[dir.clone(), stdin.clone()]).unwrap(),
+            vec![dir.join("a.txt.gz"), dir.join("b.txt"), stdin]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    fmt::Debug,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign},
+};
+
+/// A `Copy` set of the integers below `64 * WORDS`, one bit each
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitSet<const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    /// Largest number of elements, values go from 0 up to `CAPACITY - 1`
+    pub const CAPACITY: usize = WORDS * 64;
+
+    /// An empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Adds `value`, returning whether it was missing
+    ///
+    /// # Panics
+    ///
+    /// When `value` is `CAPACITY` or more
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::locate(value);
+        let missing = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        missing
+    }
+
+    /// Removes `value`, returning whether it was there
+    ///
+    /// # Panics
+    ///
+    /// When `value` is `CAPACITY` or more
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::locate(value);
+        let present = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        present
+    }
+
+    /// Adds `value` when it's missing, removes it otherwise
+    ///
+    /// # Panics
+    ///
+    /// When `value` is `CAPACITY` or more
+    pub fn toggle(&mut self, value: usize) {
+        let (word, bit) = Self::locate(value);
+        self.words[word] ^= bit;
+    }
+
+    /// Whether `value` is in the set
+    #[must_use]
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && {
+            let (word, bit) = Self::locate(value);
+            self.words[word] & bit != 0
+        }
+    }
+
+    /// Number of elements
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Whether there are no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Elements in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+
+    /// The words holding the set, element `i` is bit `i % 64` of word `i / 64`
+    #[must_use]
+    pub fn words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    fn locate(value: usize) -> (usize, u64) {
+        assert!(
+            value < Self::CAPACITY,
+            "{value} doesn't fit in a set of {} bits",
+            Self::CAPACITY
+        );
+        (value / 64, 1 << (value % 64))
+    }
+}
+
+impl BitSet {
+    /// The set as a single mask, element `i` is bit `i`
+    #[must_use]
+    pub fn bits(&self) -> u64 {
+        self.words[0]
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<u64> for BitSet {
+    fn from(bits: u64) -> Self {
+        Self { words: [bits] }
+    }
+}
+
+/// Written like a set of numbers, `{1, 3}`
+impl<const WORDS: usize> Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for BitSet<WORDS> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        impl<const WORDS: usize> $assign for BitSet<WORDS> {
+            fn $assign_method(&mut self, rhs: Self) {
+                for (word, rhs) in self.words.iter_mut().zip(rhs.words) {
+                    word.$assign_method(rhs);
+                }
+            }
+        }
+
+        impl<const WORDS: usize> $op for BitSet<WORDS> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elements() {
+        let mut set: BitSet<2> = [3, 1, 100].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1, 3, 100]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(100) && !set.contains(99) && !set.contains(500));
+        assert!(!set.insert(3));
+        assert!(set.remove(3));
+        set.toggle(1);
+        set.toggle(64);
+        assert_eq!(format!("{set:?}"), "{64, 100}");
+        assert_eq!(BitSet::<2>::CAPACITY, 128);
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = BitSet::from(0b0110);
+        let b = BitSet::from(0b1100);
+        assert_eq!((a ^ b).bits(), 0b1010);
+        assert_eq!((a & b).bits(), 0b0100);
+        assert_eq!((a | b).bits(), 0b1110);
+        assert!((a ^ a).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit")]
+    fn test_capacity() {
+        BitSet::<1>::new().insert(64);
+    }
+}
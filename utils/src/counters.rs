@@ -0,0 +1,104 @@
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+/// Up to `N` counters in a fixed array, used as a slice of the ones in use
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Counters<const N: usize = 16> {
+    len: usize,
+    counts: [u32; N],
+}
+
+impl<const N: usize> Counters<N> {
+    /// Largest number of counters
+    pub const CAPACITY: usize = N;
+
+    /// No counters at all
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            counts: [0; N],
+        }
+    }
+
+    /// `len` counters at zero, `None` when they don't fit
+    #[must_use]
+    pub fn zeros(len: usize) -> Option<Self> {
+        (len <= N).then_some(Self {
+            len,
+            counts: [0; N],
+        })
+    }
+
+    /// Adds a counter at the end, `Err` with the count when it's full
+    ///
+    /// # Errors
+    ///
+    /// When there already are `N` counters
+    pub fn push(&mut self, count: u32) -> Result<(), u32> {
+        let slot = self.counts.get_mut(self.len).ok_or(count)?;
+        *slot = count;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Counters<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for Counters<N> {
+    type Target = [u32];
+
+    fn deref(&self) -> &[u32] {
+        &self.counts[..self.len]
+    }
+}
+
+impl<const N: usize> DerefMut for Counters<N> {
+    fn deref_mut(&mut self) -> &mut [u32] {
+        &mut self.counts[..self.len]
+    }
+}
+
+impl<const N: usize> Debug for Counters<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize> TryFrom<&[u32]> for Counters<N> {
+    type Error = usize;
+
+    /// Copies the counts, `Err` with their number when there are too many
+    fn try_from(counts: &[u32]) -> Result<Self, usize> {
+        let mut counters = Self::zeros(counts.len()).ok_or(counts.len())?;
+        counters.copy_from_slice(counts);
+        Ok(counters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let mut counters = Counters::<3>::try_from([3, 5].as_slice()).unwrap();
+        counters[1] -= 2;
+        assert_eq!(counters.push(7), Ok(()));
+        assert_eq!(counters.push(9), Err(9));
+        assert_eq!(&*counters, [3, 3, 7]);
+        assert_eq!(format!("{counters:?}"), "[3, 3, 7]");
+        assert_eq!(Counters::<3>::try_from([1, 2, 3, 4].as_slice()), Err(4));
+        assert_eq!(
+            Counters::<3>::zeros(2).map(|c| c.iter().sum::<u32>()),
+            Some(0)
+        );
+        assert_ne!(Counters::<3>::zeros(2), Counters::zeros(1));
+    }
+}
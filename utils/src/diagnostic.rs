@@ -0,0 +1,288 @@
+use nom::error::{self, ErrorKind};
+use std::fmt::Display;
+
+use crate::ParseResult;
+
+/// What the parser wanted to see where it stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    Kind(ErrorKind),
+    Char(char),
+    Message(&'static str),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Kind(ErrorKind::Eof) => write!(f, "expected end of input"),
+            Self::Kind(kind) => write!(f, "expected {}", kind.description().to_lowercase()),
+            Self::Char(c) => write!(f, "expected {c:?}"),
+            Self::Message(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// Error produced by the day parsers
+///
+/// Keeps the remaining input where parsing failed and every
+/// [`nom::error::context`] label it bubbled up through, so it can be turned
+/// into a [`Diagnostic`] pointing into the original input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<'a> {
+    input: &'a str,
+    expected: Expected,
+    contexts: Vec<(&'a str, &'static str)>,
+}
+
+impl<'a> ParseError<'a> {
+    /// Error for a failure nom has no [`ErrorKind`] for
+    #[must_use]
+    pub fn custom(input: &'a str, message: &'static str) -> Self {
+        Self {
+            input,
+            expected: Expected::Message(message),
+            contexts: Vec::new(),
+        }
+    }
+
+    /// Locates the error in `source`, the full text handed to the parser
+    #[must_use]
+    pub fn diagnose(&self, source: &str) -> Diagnostic {
+        Diagnostic {
+            location: Location::find(source, self.input),
+            message: self.expected.to_string(),
+            contexts: self
+                .contexts
+                .iter()
+                .map(|(input, label)| ((*label).to_string(), Location::find(source, input)))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> error::ParseError<&'a str> for ParseError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Expected::Kind(kind),
+            contexts: Vec::new(),
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self {
+            input,
+            expected: Expected::Char(c),
+            contexts: Vec::new(),
+        }
+    }
+}
+
+impl<'a> error::ContextError<&'a str> for ParseError<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, mut other: Self) -> Self {
+        other.contexts.push((input, ctx));
+        other
+    }
+}
+
+impl<'a, E> error::FromExternalError<&'a str, E> for ParseError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        <Self as error::ParseError<&'a str>>::from_error_kind(input, kind)
+    }
+}
+
+/// Line and column (both 1-based) of a position in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    line: usize,
+    column: usize,
+    text: String,
+}
+
+impl Location {
+    /// Finds where `rest` starts in `source`
+    ///
+    /// `rest` is expected to be a suffix of `source`, which is what nom
+    /// parsers hand around.
+    fn find(source: &str, rest: &str) -> Self {
+        let offset = source.len().saturating_sub(rest.len());
+        let offset = (0..=offset)
+            .rev()
+            .find(|&i| source.is_char_boundary(i))
+            .unwrap_or_default();
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        Self {
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            text: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+}
+
+/// A parse failure located in the input, ready to be shown to a human
+///
+/// ```text
+/// expected ',' at line 2, column 4
+///   while parsing point at line 2, column 1
+///   |
+/// 2 | 57;618,57
+///   |   ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    location: Location,
+    message: String,
+    contexts: Vec<(String, Location)>,
+}
+
+impl Diagnostic {
+    /// Line of the failure, starting at 1
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.location.line
+    }
+
+    /// Column of the failure in characters, starting at 1
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.location.column
+    }
+
+    /// Context labels from the innermost to the outermost
+    pub fn contexts(&self) -> impl Iterator<Item = &str> {
+        self.contexts.iter().map(|(label, _)| label.as_str())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Location { line, column, text } = &self.location;
+        writeln!(f, "{} at line {line}, column {column}", self.message)?;
+        for (label, Location { line, column, .. }) in &self.contexts {
+            writeln!(f, "  while parsing {label} at line {line}, column {column}")?;
+        }
+        let gutter = " ".repeat(line.to_string().len());
+        let caret: String = text
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(f, "{gutter} | {caret}^")
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// What to do with input the parser left behind
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingInput {
+    /// Anything but whitespace left over is an error
+    #[default]
+    Reject,
+    /// Left over input is dropped, for days that deliberately stop early
+    Ignore,
+}
+
+/// Finishes parsing `source`, turning a failure into a [`Diagnostic`]
+///
+/// # Errors
+///
+/// When the parser failed or, with [`TrailingInput::Reject`], when anything
+/// but whitespace was left unparsed
+pub fn finish_parse<'a, T>(
+    source: &str,
+    parsed: ParseResult<'a, T>,
+    trailing: TrailingInput,
+) -> Result<T, Diagnostic> {
+    let (rest, parsed) = nom::Finish::finish(parsed).map_err(|e| e.diagnose(source))?;
+    let rest = rest.trim_start();
+    if trailing == TrailingInput::Reject && !rest.is_empty() {
+        return Err(
+            <ParseError as error::ParseError<&str>>::from_error_kind(rest, ErrorKind::Eof)
+                .diagnose(source),
+        );
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(input: &str) -> ParseResult<'_, Vec<u64>> {
+        use nom::{
+            bytes::complete::tag, character::complete::u64, error::context, multi::separated_list1,
+            Parser,
+        };
+
+        context("point", separated_list1(tag(","), u64)).parse(input)
+    }
+
+    fn points(input: &str) -> ParseResult<'_, Vec<Vec<u64>>> {
+        use nom::{
+            character::complete::line_ending, combinator::all_consuming, error::context,
+            multi::separated_list1, Parser,
+        };
+
+        all_consuming(context("points", separated_list1(line_ending, point))).parse(input)
+    }
+
+    #[test]
+    fn test_location() {
+        let source = "162,817,812\n57,618;57\n906,360,560\n";
+        let error = nom::Finish::finish(points(source)).unwrap_err();
+        let diagnostic = error.diagnose(source);
+        assert_eq!((diagnostic.line(), diagnostic.column()), (2, 7));
+        assert_eq!(
+            diagnostic.to_string(),
+            "expected end of input at line 2, column 7
+  |
+2 | 57,618;57
+  |       ^"
+        );
+    }
+
+    #[test]
+    fn test_contexts() {
+        let source = "162,817,812\nx,57";
+        let error = nom::Finish::finish(point(&source[12..])).unwrap_err();
+        let diagnostic = error.diagnose(source);
+        assert_eq!(diagnostic.contexts().collect::<Vec<_>>(), vec!["point"]);
+        assert_eq!(
+            diagnostic.to_string(),
+            "expected digit at line 2, column 1
+  while parsing point at line 2, column 1
+  |
+2 | x,57
+  | ^"
+        );
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let source = "1,2\n3,4\n\n5,6\n";
+        let error = finish_parse(source, point(source), TrailingInput::Reject).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+        let error = finish_parse("1,2 \n  x", point("1,2 \n  x"), TrailingInput::Reject);
+        assert_eq!(error.map_err(|e| (e.line(), e.column())), Err((2, 3)));
+        assert_eq!(
+            finish_parse(source, point(source), TrailingInput::Ignore),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            finish_parse("1,2\n\n", point("1,2\n\n"), TrailingInput::Reject),
+            Ok(vec![1, 2])
+        );
+    }
+}
@@ -0,0 +1,137 @@
+/// Union-find over the elements `0..len`, tracking the size of every component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a component of its own
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// Number of elements
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Whether there are no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the component holding `x`
+    ///
+    /// # Panics
+    ///
+    /// When `x` isn't an element
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`, `false` when they already were one
+    ///
+    /// # Panics
+    ///
+    /// When `a` or `b` isn't an element
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] < self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same component
+    ///
+    /// # Panics
+    ///
+    /// When `a` or `b` isn't an element
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component holding `x`
+    ///
+    /// # Panics
+    ///
+    /// When `x` isn't an element
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of components
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Size of every component, in no particular order
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parent
+            .iter()
+            .enumerate()
+            .filter(|&(x, &parent)| x == parent)
+            .map(|(root, _)| self.size[root])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.component_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.size(5), 1);
+        assert_eq!(set.component_count(), 3);
+        let mut sizes = set.component_sizes().collect::<Vec<_>>();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 4]);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let mut set = DisjointSet::new(100_000);
+        for x in 1..set.len() {
+            set.union(x - 1, x);
+        }
+        assert_eq!(set.component_count(), 1);
+        assert_eq!(set.size(0), 100_000);
+        assert_eq!(set.find(99_999), set.find(0));
+    }
+}
@@ -0,0 +1,466 @@
+use nom::{bytes::complete::tag, Parser};
+use std::{
+    array,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Index, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::{ParseError, ParseResult};
+
+/// Integer type usable as a coordinate
+pub trait Coord:
+    Copy
+    + Ord
+    + Default
+    + Hash
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    /// The multiplicative identity
+    const ONE: Self;
+
+    /// Parses a coordinate written in decimal
+    ///
+    /// # Errors
+    ///
+    /// When the input doesn't start with a number that fits the type
+    fn parse(input: &str) -> ParseResult<'_, Self>;
+
+    /// Distance between `self` and `other` along one axis
+    #[must_use]
+    fn distance(self, other: Self) -> Self;
+
+    /// `self` moved by `delta` (-1, 0 or 1), `None` when that leaves the type's range
+    #[must_use]
+    fn step(self, delta: i8) -> Option<Self>;
+
+    /// `self + other`, `None` when it overflows
+    #[must_use]
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// `self - other`, `None` when it overflows
+    #[must_use]
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// `self * other`, `None` when it overflows
+    #[must_use]
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ident)*) => {$(
+        impl Coord for $t {
+            const ONE: Self = 1;
+
+            fn parse(input: &str) -> ParseResult<'_, Self> {
+                nom::character::complete::$t(input)
+            }
+
+            fn distance(self, other: Self) -> Self {
+                self.max(other) - self.min(other)
+            }
+
+            fn step(self, delta: i8) -> Option<Self> {
+                match delta {
+                    -1 => self.checked_sub(1),
+                    1 => self.checked_add(1),
+                    _ => Some(self),
+                }
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                $t::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                $t::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                $t::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_coord! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+
+/// A location in `N` dimensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+/// A displacement between two [`Point`]s
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+pub type Point2<T = i64> = Point<T, 2>;
+pub type Point3<T = i64> = Point<T, 3>;
+pub type Vector2<T = i64> = Vector<T, 2>;
+pub type Vector3<T = i64> = Vector<T, 3>;
+
+impl<T: Coord, const N: usize> Point<T, N> {
+    /// Point at every coordinate zero
+    #[must_use]
+    pub fn origin() -> Self {
+        Self([T::default(); N])
+    }
+
+    /// Sum of the distances along every axis
+    #[must_use]
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.distances(other).fold(T::default(), |sum, d| sum + d)
+    }
+
+    /// Largest distance along any axis
+    #[must_use]
+    pub fn chebyshev(&self, other: &Self) -> T {
+        self.distances(other).max().unwrap_or_default()
+    }
+
+    /// Square of the straight line distance
+    #[must_use]
+    pub fn square_distance(&self, other: &Self) -> T {
+        self.distances(other)
+            .fold(T::default(), |sum, d| sum + d * d)
+    }
+
+    fn distances<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = T> + 'a {
+        self.0.iter().zip(&other.0).map(|(&a, &b)| a.distance(b))
+    }
+
+    /// Points one step away along a single axis
+    ///
+    /// Points that don't fit in `T` are skipped.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].into_iter().filter_map(move |delta| {
+                let mut coords = self.0;
+                coords[axis] = coords[axis].step(delta)?;
+                Some(Self(coords))
+            })
+        })
+    }
+
+    /// Points one step away along any axes, diagonals included
+    ///
+    /// Points that don't fit in `T` are skipped.
+    pub fn surrounding(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(u32::try_from(N).expect("too many dimensions"));
+        (0..count)
+            .filter(move |&offset| offset != count / 2)
+            .filter_map(move |mut offset| {
+                let mut coords = self.0;
+                for coord in &mut coords {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                    let delta = (offset % 3) as i8 - 1;
+                    *coord = coord.step(delta)?;
+                    offset /= 3;
+                }
+                Some(Self(coords))
+            })
+    }
+}
+
+impl<T: Copy> Point<T, 2> {
+    #[must_use]
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    #[must_use]
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> Point<T, 3> {
+    #[must_use]
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    #[must_use]
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    #[must_use]
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T: Coord, const N: usize> Vector<T, N> {
+    /// Vector of length zero
+    #[must_use]
+    pub fn zero() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Point<T, N> {
+    fn from(coords: [T; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(coords: [T; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T: Coord, const N: usize> Add<Vector<T, N>> for Point<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Vector<T, N>) -> Self {
+        Self(array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: Coord, const N: usize> AddAssign<Vector<T, N>> for Point<T, N> {
+    fn add_assign(&mut self, rhs: Vector<T, N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord, const N: usize> Sub<Vector<T, N>> for Point<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector<T, N>) -> Self {
+        Self(array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Coord, const N: usize> SubAssign<Vector<T, N>> for Point<T, N> {
+    fn sub_assign(&mut self, rhs: Vector<T, N>) {
+        *self = *self - rhs;
+    }
+}
+
+/// Vector from `rhs` to `self`
+impl<T: Coord, const N: usize> Sub for Point<T, N> {
+    type Output = Vector<T, N>;
+
+    fn sub(self, rhs: Self) -> Vector<T, N> {
+        Vector(array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Coord, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: Coord, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Coord, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<T: Coord + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+/// Coordinates separated by commas, the way puzzle inputs write them
+impl<T: Display, const N: usize> Display for Point<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, coord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{coord}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An axis-aligned box, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<T, const N: usize> {
+    min: Point<T, N>,
+    max: Point<T, N>,
+}
+
+impl<T: Coord, const N: usize> Bounds<T, N> {
+    /// Box spanned by two opposite corners, in any order
+    #[must_use]
+    pub fn new(a: Point<T, N>, b: Point<T, N>) -> Self {
+        Self {
+            min: Point(array::from_fn(|i| a.0[i].min(b.0[i]))),
+            max: Point(array::from_fn(|i| a.0[i].max(b.0[i]))),
+        }
+    }
+
+    /// Corner with the smallest coordinates
+    #[must_use]
+    pub fn min(&self) -> Point<T, N> {
+        self.min
+    }
+
+    /// Corner with the largest coordinates
+    #[must_use]
+    pub fn max(&self) -> Point<T, N> {
+        self.max
+    }
+
+    /// Whether `point` is inside the box or on its edge
+    #[must_use]
+    pub fn contains(&self, point: &Point<T, N>) -> bool {
+        (0..N).all(|i| (self.min.0[i]..=self.max.0[i]).contains(&point.0[i]))
+    }
+
+    /// Number of points along every axis, `None` when one doesn't fit in `T`
+    #[must_use]
+    pub fn size(&self) -> Option<Vector<T, N>> {
+        let mut size = [T::ONE; N];
+        for (i, side) in size.iter_mut().enumerate() {
+            *side = Coord::checked_sub(self.max.0[i], self.min.0[i])?.checked_add(T::ONE)?;
+        }
+        Some(Vector(size))
+    }
+
+    /// Number of points inside the box, edges included, `None` when it
+    /// doesn't fit in `T`
+    #[must_use]
+    pub fn volume(&self) -> Option<T> {
+        self.size()?
+            .0
+            .into_iter()
+            .try_fold(T::ONE, Coord::checked_mul)
+    }
+}
+
+impl<T: Coord> Bounds<T, 2> {
+    /// Number of points inside the rectangle, edges included, `None` when it
+    /// doesn't fit in `T`
+    #[must_use]
+    pub fn area(&self) -> Option<T> {
+        self.volume()
+    }
+}
+
+/// Parses `N` coordinates separated by `separator`
+pub fn point<'a, T: Coord, const N: usize>(
+    separator: &'static str,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Point<T, N>> {
+    move |mut input: &'a str| {
+        let mut coords = [T::default(); N];
+        for (i, coord) in coords.iter_mut().enumerate() {
+            if i > 0 {
+                (input, _) = tag::<_, _, ParseError<'a>>(separator).parse(input)?;
+            }
+            (input, *coord) = T::parse(input)?;
+        }
+        Ok((input, Point(coords)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            point::<u64, 3>(",")("162,817,812\n"),
+            Ok(("\n", Point([162, 817, 812])))
+        );
+        assert_eq!(point::<i32, 2>(", ")("-3, 4"), Ok(("", Point([-3, 4]))));
+        assert!(point::<u8, 2>(",")("1,x").is_err());
+        assert_eq!(Point([7, 1]).to_string(), "7,1");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::from([1, 2]);
+        let b = Point2::from([4, -2]);
+        let v = b - a;
+        assert_eq!(v, Vector([3, -4]));
+        assert_eq!(a + v, b);
+        assert_eq!(b - v, a);
+        assert_eq!(-v * 2, Vector([-6, 8]));
+        assert_eq!(v + Vector::zero(), v);
+        let mut c = a;
+        c += v;
+        c -= v;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point3::<u64>::from([162, 817, 812]);
+        let b = Point3::from([425, 690, 689]);
+        assert_eq!(a.manhattan(&b), 263 + 127 + 123);
+        assert_eq!(a.chebyshev(&b), 263);
+        assert_eq!(a.square_distance(&b), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let corner = Point2::<usize>::origin();
+        assert_eq!(
+            corner.neighbors().collect::<Vec<_>>(),
+            [Point([1, 0]), Point([0, 1])]
+        );
+        assert_eq!(corner.surrounding().count(), 3);
+        assert_eq!(Point3::<i8>::origin().neighbors().count(), 6);
+        assert_eq!(Point3::<i8>::origin().surrounding().count(), 26);
+        assert!(Point2::<i8>::origin()
+            .surrounding()
+            .all(|p| p.chebyshev(&Point::origin()) == 1));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let rect = Bounds::new(Point([11, 1]), Point([2, 5]));
+        assert_eq!((rect.min(), rect.max()), (Point([2, 1]), Point([11, 5])));
+        assert_eq!(rect.area(), Some(50));
+        assert!(rect.contains(&Point([11, 3])));
+        assert!(!rect.contains(&Point([12, 3])));
+        let cube = Bounds::new(Point([0u8, 0, 0]), Point([1, 2, 3]));
+        assert_eq!(cube.volume(), Some(24));
+        let wide = Bounds::new(Point([0u8, 0]), Point([255, 0]));
+        assert_eq!(wide.size(), None);
+        let big = Bounds::new(Point([0u8, 0]), Point([15, 16]));
+        assert_eq!(big.area(), None);
+        assert_eq!(Bounds::new(Point([-100i8]), Point([100])).size(), None);
+    }
+}
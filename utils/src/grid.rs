@@ -0,0 +1,318 @@
+use nom::error::{ErrorKind, ParseError as _};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{ParseError, ParseResult};
+
+/// Position in a grid as `(row, col)`
+pub type Pos = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, `None` when the rows have different widths
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Number of columns
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Cell at `pos`, `None` outside of the grid
+    #[must_use]
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    /// Mutable cell at `pos`, `None` outside of the grid
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// Every position from the top left, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(|row| (0..self.width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Row `row` from left to right
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Rows from top to bottom
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Column `col` from top to bottom
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(col)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    /// Columns from left to right
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Grid with rows and columns swapped
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Grid of the same shape with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Positions up, left, right and down of `pos` that are inside the grid
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &ORTHOGONAL)
+    }
+
+    /// Positions around `pos`, diagonals included, that are inside the grid
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &SURROUNDING)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let row = row.checked_add_signed(dr).filter(|&r| r < self.height)?;
+            let col = col.checked_add_signed(dc).filter(|&c| c < self.width)?;
+            Some((row, col))
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+/// One line per row, cells written next to each other
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses lines of characters into a grid, mapping every character with `cell`
+///
+/// Stops at the first blank line or the end of the input. Characters `cell`
+/// returns `None` for and rows of different widths are errors.
+pub fn grid<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Grid<T>> {
+    move |input: &'a str| {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        let mut rest = input;
+        loop {
+            let line_end = rest.find('\n').unwrap_or(rest.len());
+            let line = rest[..line_end].trim_end_matches('\r');
+            if line.is_empty() {
+                break;
+            }
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                let Some(value) = cell(c) else {
+                    return Err(nom::Err::Error(ParseError::custom(
+                        &rest[i..],
+                        "unexpected character in grid",
+                    )));
+                };
+                cells.push(value);
+            }
+            let row_width = cells.len() - row_start;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(nom::Err::Error(ParseError::custom(
+                    rest,
+                    "grid rows must all have the same width",
+                )));
+            }
+            height += 1;
+            rest = &rest[(line_end + 1).min(rest.len())..];
+        }
+        match width {
+            Some(width) => Ok((
+                rest,
+                Grid {
+                    width,
+                    height,
+                    cells,
+                },
+            )),
+            None => Err(nom::Err::Error(ParseError::from_error_kind(
+                input,
+                ErrorKind::Many1,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{finish_parse, TrailingInput};
+
+    const INPUT: &str = "..@\n@.@\n";
+
+    fn parse(input: &str) -> ParseResult<'_, Grid<bool>> {
+        grid(|c| match c {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })(input)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            finish_parse(INPUT, parse(INPUT), TrailingInput::Reject),
+            Ok(Grid::from_rows(vec![vec![false, false, true], vec![true, false, true]]).unwrap())
+        );
+        let (rest, grid) = parse("..@\n\n123").unwrap();
+        assert_eq!((rest, grid.height()), ("\n123", 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = finish_parse("..@\n@.\n", parse("..@\n@.\n"), TrailingInput::Reject);
+        assert_eq!(error.map_err(|e| (e.line(), e.column())), Err((2, 1)));
+        let error = finish_parse("..@\n@x@\n", parse("..@\n@x@\n"), TrailingInput::Reject);
+        assert_eq!(error.map_err(|e| (e.line(), e.column())), Err((2, 2)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8((1, 1)).collect::<Vec<_>>(),
+            [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+        assert_eq!(
+            grid.transpose(),
+            Grid::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]]).unwrap()
+        );
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::from_rows(vec![vec!['.', '@'], vec!['@', '.']]).unwrap();
+        assert_eq!(grid.to_string(), ".@\n@.");
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Context;
+use flate2::read::MultiGzDecoder;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use crate::AocResult;
+
+/// Reads a puzzle input
+///
+/// `-` reads from stdin. Files ending in `.gz` or `.zst` are decompressed
+/// while reading, anything else is read as plain text.
+///
+/// # Errors
+///
+/// When the input can't be opened, decompressed or isn't UTF-8
+pub fn read_input(path: &Path) -> AocResult<String> {
+    let mut input = String::new();
+    open(path)
+        .and_then(|mut reader| reader.read_to_string(&mut input))
+        .with_context(|| format!("can't read {}", path.display()))?;
+    Ok(input)
+}
+
+fn open(path: &Path) -> io::Result<Box<dyn Read>> {
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = BufReader::new(File::open(path)?);
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => Box::new(MultiGzDecoder::new(file)),
+        Some("zst") => Box::new(zstd::Decoder::with_buffer(file)?),
+        _ => Box::new(file),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const INPUT: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n";
+
+    fn temp_file(name: &str, content: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("aoc-utils-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_plain() {
+        let path = temp_file("input.txt", INPUT.as_bytes());
+        assert_eq!(read_input(&path).unwrap(), INPUT);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_gzip() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(INPUT.as_bytes()).unwrap();
+        let path = temp_file("input.txt.gz", &encoder.finish().unwrap());
+        assert_eq!(read_input(&path).unwrap(), INPUT);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_zstd() {
+        let path = temp_file(
+            "input.txt.zst",
+            &zstd::encode_all(INPUT.as_bytes(), 0).unwrap(),
+        );
+        assert_eq!(read_input(&path).unwrap(), INPUT);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing() {
+        let error = read_input(Path::new("does/not/exist.txt")).unwrap_err();
+        assert_eq!(error.to_string(), "can't read does/not/exist.txt");
+    }
+}
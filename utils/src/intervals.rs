@@ -0,0 +1,277 @@
+use std::ops::RangeInclusive;
+
+/// Integers an [`IntervalSet`] can hold
+pub trait Endpoint: Copy + Ord {
+    /// The value right above, `None` for the largest one
+    fn next(self) -> Option<Self>;
+
+    /// The value right below, `None` for the smallest one
+    fn prev(self) -> Option<Self>;
+
+    /// Number of values from `self` up to `end`, both included
+    fn count_to(self, end: Self) -> u128;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                fn next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count_to(self, end: Self) -> u128 {
+                    (end as i128 - self as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers stored as sorted, disjoint ranges
+///
+/// Ranges that overlap or touch are merged as they're added, so every value
+/// belongs to exactly one range and no two ranges are adjacent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    /// An empty set
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or touches
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.next().is_some_and(|next| next < start));
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| end.next().is_none_or(|next| s <= next));
+        let merged = if first == last {
+            (start, end)
+        } else {
+            (
+                self.ranges[first].0.min(start),
+                self.ranges[last - 1].1.max(end),
+            )
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Whether `value` is in the set
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.ranges.partition_point(|(_, e)| e < value);
+        self.ranges.get(i).is_some_and(|(s, _)| s <= value)
+    }
+
+    /// Number of values in the set
+    #[must_use]
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| s.count_to(e)).sum()
+    }
+
+    /// Whether the set has no values
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges in increasing order
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Values in either set
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.ranges());
+        union
+    }
+
+    /// Values in both sets
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(&&(a_start, a_end)), Some(&&(b_start, b_end))) = (a.peek(), b.peek()) {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a_end < b_end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Values in `self` but not in `other`
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+        for &(start, end) in &self.ranges {
+            let mut start = Some(start);
+            while let Some(&&(o_start, o_end)) = others.peek() {
+                let Some(s) = start.filter(|&s| s <= end) else {
+                    break;
+                };
+                if o_end < s {
+                    others.next();
+                    continue;
+                }
+                if o_start > end {
+                    break;
+                }
+                if o_start > s {
+                    ranges.push((s, o_start.prev().expect("o_start is above s")));
+                }
+                start = o_end.next();
+                if o_end <= end {
+                    others.next();
+                }
+            }
+            if let Some(s) = start.filter(|&s| s <= end) {
+                ranges.push((s, end));
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Values of `bounds` that aren't in the set
+    #[must_use]
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+}
+
+impl<T: Endpoint> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Endpoint> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const DOMAIN: u8 = 64;
+
+    /// A set together with the plain booleans it should match
+    fn random_set(rng: &mut StdRng) -> (IntervalSet<u8>, [bool; DOMAIN as usize]) {
+        let mut set = IntervalSet::new();
+        let mut model = [false; DOMAIN as usize];
+        for _ in 0..rng.gen_range(0..6) {
+            let start = rng.gen_range(0..DOMAIN);
+            let end = rng.gen_range(start..DOMAIN);
+            set.insert(start..=end);
+            model[start as usize..=end as usize].fill(true);
+        }
+        (set, model)
+    }
+
+    fn assert_matches(set: &IntervalSet<u8>, model: &[bool; DOMAIN as usize]) {
+        for pair in set.ranges.windows(2) {
+            assert!(pair[0].1 + 1 < pair[1].0, "{set:?} isn't normalized");
+        }
+        for value in 0..DOMAIN {
+            assert_eq!(
+                set.contains(&value),
+                model[value as usize],
+                "{value} in {set:?}"
+            );
+        }
+        let count = model.iter().filter(|&&v| v).count();
+        assert_eq!(set.len(), count as u128);
+        assert_eq!(set.is_empty(), count == 0);
+    }
+
+    #[test]
+    fn test_insert() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), [3..=5, 10..=20]);
+        let set: IntervalSet<u64> = [1..=2, 5..=6, 3..=4].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), [1..=6]);
+        let set: IntervalSet<u8> = [250..=255, 0..=0, 1..=249].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), [0..=255]);
+        assert_eq!(set.len(), 256);
+        let set: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(set.len(), 1 << 64);
+        let set: IntervalSet<i64> = [i64::MIN..=-1, 0..=i64::MAX].into_iter().collect();
+        assert_eq!(set.len(), 1 << 64);
+        #[allow(clippy::reversed_empty_ranges)]
+        let set: IntervalSet<i32> = [5..=4].into_iter().collect();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_operations() {
+        let a: IntervalSet<i32> = [-5..=5, 10..=20].into_iter().collect();
+        let b: IntervalSet<i32> = [0..=12, 20..=30].into_iter().collect();
+        let ranges = |set: IntervalSet<i32>| set.ranges().collect::<Vec<_>>();
+        assert_eq!(ranges(a.union(&b)), [-5..=30]);
+        assert_eq!(ranges(a.intersection(&b)), [0..=5, 10..=12, 20..=20]);
+        assert_eq!(ranges(a.difference(&b)), [-5..=-1, 13..=19]);
+        assert_eq!(ranges(a.complement(-10..=25)), [-10..=-6, 6..=9, 21..=25]);
+    }
+
+    #[test]
+    fn test_properties() {
+        let mut rng = StdRng::seed_from_u64(2025);
+        for _ in 0..2000 {
+            let (a, a_model) = random_set(&mut rng);
+            let (b, b_model) = random_set(&mut rng);
+            assert_matches(&a, &a_model);
+            let combine =
+                |f: fn(bool, bool) -> bool| std::array::from_fn(|i| f(a_model[i], b_model[i]));
+            assert_matches(&a.union(&b), &combine(|a, b| a || b));
+            assert_matches(&a.intersection(&b), &combine(|a, b| a && b));
+            assert_matches(&a.difference(&b), &combine(|a, b| a && !b));
+
+            let start = rng.gen_range(0..DOMAIN);
+            let end = rng.gen_range(start..DOMAIN);
+            let bounds = start as usize..=end as usize;
+            let complement = std::array::from_fn(|i| bounds.contains(&i) && !a_model[i]);
+            assert_matches(&a.complement(start..=end), &complement);
+        }
+    }
+}
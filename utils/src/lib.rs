@@ -1,29 +1,113 @@
-pub use anyhow::Result as AocResult;
-pub use clap::Parser;
-use clap::{builder::PossibleValue, ValueEnum};
-use std::path::PathBuf;
+mod answer;
+mod batch;
+pub mod bitset;
+pub mod counters;
+mod diagnostic;
+pub mod dsu;
+pub mod geom;
+pub mod grid;
+mod input;
+pub mod intervals;
+pub mod linear;
+mod progress;
+mod report;
+pub mod search;
+mod solution;
+mod timing;
 
-pub type ParseResult<'a, T> = nom::IResult<&'a str, T>;
+pub use answer::{Answer, AnswerKey, ExpectedAnswer};
+pub use anyhow::{anyhow, bail, Context, Result as AocResult};
+pub use batch::{expand_inputs, Batch};
+pub use clap::Parser;
+use clap::{builder::PossibleValue, Args, ValueEnum};
+pub use diagnostic::{finish_parse, Diagnostic, ParseError, TrailingInput};
+pub use input::read_input;
+pub use progress::Progress;
+pub use report::{OutputFormat, Report};
+pub use solution::{solve, solve_with, ParseFn, Registry, Solution, SolveFn};
+use std::path::{Path, PathBuf};
+pub use timing::Timings;
 
-#[derive(Debug, Parser)]
-#[command(author, about, long_about = None)]
-pub struct Cli {
-    /// Input file
-    input_file: PathBuf,
+pub type ParseResult<'a, T> = nom::IResult<&'a str, T, ParseError<'a>>;
 
+/// Options shared by every way of running a day
+#[derive(Debug, Clone, Args)]
+pub struct RunOptions {
     /// Part of the task to do
     #[arg(short, long, value_enum, default_value_t)]
     part: SolutionPart,
+
+    /// Report how long each phase took
+    #[arg(short, long)]
+    time: bool,
+
+    /// How the answers are printed
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Check the answers against `answers.toml` next to the input file
+    #[arg(long)]
+    verify: bool,
 }
 
-impl Cli {
-    /// Reads input file and returns its content
+impl RunOptions {
+    /// Runs `solve` on a single input and prints its report
+    ///
+    /// `solve` gets the content of the input and the report to record
+    /// timings and answers in.
+    ///
+    /// # Errors
+    ///
+    /// When the input can't be read, solved or verified
+    pub fn run_single(
+        &self,
+        day: u8,
+        path: &Path,
+        solve: impl Fn(&str, &mut Report) -> AocResult<()>,
+    ) -> AocResult<()> {
+        let answer_key = if self.verify {
+            Some(AnswerKey::load(&path.with_file_name("answers.toml"))?)
+        } else {
+            None
+        };
+        progress::enable(self.format == OutputFormat::Text);
+        let mut report = Report::new(day, self.format, self.time);
+        let input = report.time("Read", || read_input(path))?;
+        solve(&input, &mut report)?;
+        if let Some(key) = &answer_key {
+            report.verify(key);
+        }
+        report.finish()
+    }
+
+    /// Runs `solve` on every input and prints a summary
     ///
     /// # Errors
     ///
-    /// Check errors for [`std::fs::read_to_string`]
-    pub fn input(&self) -> AocResult<String> {
-        Ok(std::fs::read_to_string(&self.input_file)?)
+    /// When `--verify` was given or any of the inputs failed
+    pub fn run_batch(
+        &self,
+        day: u8,
+        paths: &[PathBuf],
+        solve: impl Fn(&str, &mut Report) -> AocResult<()>,
+    ) -> AocResult<()> {
+        anyhow::ensure!(!self.verify, "--verify needs a single input file");
+        progress::enable(self.format == OutputFormat::Text);
+        let mut batch = Batch::default();
+        for path in expand_inputs(paths)? {
+            let mut report = Report::new(day, self.format, self.time).for_input(&path);
+            let result = report
+                .time("Read", || read_input(&path))
+                .and_then(|input| solve(&input, &mut report));
+            batch.push(report, result);
+        }
+        batch.finish(self.format)
+    }
+
+    /// Part of the task to do
+    #[must_use]
+    pub fn part(&self) -> SolutionPart {
+        self.part
     }
 
     /// Determines if the part should run based on cli flags
@@ -35,6 +119,75 @@ impl Cli {
             self.part == part
         }
     }
+
+    /// Determines if phase timings should be reported
+    #[must_use]
+    pub fn show_timings(&self) -> bool {
+        self.time
+    }
+
+    /// How the answers are printed
+    #[must_use]
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(author, about, long_about = None)]
+pub struct Cli {
+    /// Input files, `-` for stdin, `.gz` and `.zst` files are decompressed
+    ///
+    /// Several files or a directory of inputs run in batch mode and print a
+    /// summary table.
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    #[command(flatten)]
+    options: RunOptions,
+}
+
+impl Cli {
+    /// Runs solution `S` on every input and prints the results
+    ///
+    /// # Errors
+    ///
+    /// When an input can't be read, solved or verified
+    pub fn run<S: Solution>(&self) -> AocResult<()> {
+        self.run_with(S::DAY, solve::<S>)
+    }
+
+    /// Runs `solve` for `day` on every input and prints the results
+    ///
+    /// For days whose binary has options of its own, `solve` is usually
+    /// [`solve_with`] handed parts that use them.
+    ///
+    /// # Errors
+    ///
+    /// When an input can't be read, solved or verified
+    pub fn run_with(
+        &self,
+        day: u8,
+        solve: impl Fn(&str, SolutionPart, &mut Report) -> AocResult<()>,
+    ) -> AocResult<()> {
+        let run = |input: &str, report: &mut Report| solve(input, self.options.part, report);
+        match self.inputs.as_slice() {
+            [path] if !path.is_dir() => self.options.run_single(day, path, run),
+            paths => self.options.run_batch(day, paths, run),
+        }
+    }
+
+    /// Determines if the part should run based on cli flags
+    #[must_use]
+    pub fn should_run(&self, part: SolutionPart) -> bool {
+        self.options.should_run(part)
+    }
+
+    /// Input files given on the command line
+    #[must_use]
+    pub fn inputs(&self) -> &[PathBuf] {
+        &self.inputs
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -74,67 +227,44 @@ impl ValueEnum for SolutionPart {
 
 #[macro_export]
 macro_rules! aoc_main {
-    ($parse:ident, $part1:ident, $part2:ident) => {
-        fn main() -> AocResult<()> {
-            let cli = Cli::parse();
-            let input = cli.input()?;
-            let parsed = $parse(&input);
-            let (_, parsed) = aoc_main!(@finalize, parsed);
-            if cli.should_run(SolutionPart::PartOne) {
-                let part1 = $part1(&parsed)?;
-                println!("Part 1: {:#?}", part1);
-            }
-            if cli.should_run(SolutionPart::PartTwo) {
-                let part2 = $part2(&parsed)?;
-                println!("Part 2: {:#?}", part2);
-            }
-            Ok(())
+    ($solution:ty) => {
+        fn main() -> $crate::AocResult<()> {
+            <$crate::Cli as $crate::Parser>::parse().run::<$solution>()
         }
     };
-
-    ($parse1:ident, $parse2:ident, $part1:ident, $part2:ident) => {
-        fn main() -> AocResult<()> {
-            let cli = Cli::parse();
-            let input = cli.input()?;
-            if cli.should_run(SolutionPart::PartOne) {
-                let parsed = $parse1(&input);
-                let (_, parsed) = aoc_main!(@finalize, parsed);
-                let part1 = $part1(&parsed)?;
-                println!("Part 1: {:#?}", part1);
-            }
-            if cli.should_run(SolutionPart::PartTwo) {
-                let parsed = $parse2(&input);
-                let (_, parsed) = aoc_main!(@finalize, parsed);
-                let part2 = $part2(&parsed)?;
-                println!("Part 2: {:#?}", part2);
-            }
-            Ok(())
-        }
-    };
-
-    (@finalize, $parsed:expr) => {
-        nom::Finish::finish($parsed).map_err(|nom::error::Error { input, code }| {
-            nom::error::Error {
-                input: input.to_string(),
-                code,
-            }
-        })?
-    }
 }
 
 #[macro_export]
 macro_rules! assert_part {
+    (lenient: $parse:ident, $part:ident, $input:expr, $equals:expr) => {{
+        let input = $crate::assert_parser!(@finish, $crate::TrailingInput::Ignore, $parse, $input);
+        let part = $part(&input).unwrap();
+        let expected = $crate::Answer::expected(&part, $equals);
+        assert_eq!($crate::Answer::from(part), expected)
+    }};
+
     ($parse:ident, $part:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::assert_parser!(@finish, $crate::TrailingInput::Reject, $parse, $input);
         let part = $part(&input).unwrap();
-        assert_eq!(format!("{:?}", part), format!("{:?}", $equals))
+        let expected = $crate::Answer::expected(&part, $equals);
+        assert_eq!($crate::Answer::from(part), expected)
     }};
 }
 
 #[macro_export]
 macro_rules! assert_parser {
+    (lenient: $parse:ident, $input:expr, $equals:expr) => {{
+        let input = $crate::assert_parser!(@finish, $crate::TrailingInput::Ignore, $parse, $input);
+        assert_eq!(input, $equals)
+    }};
+
     ($parse:ident, $input:expr, $equals:expr) => {{
-        let input = $parse($input).unwrap().1;
+        let input = $crate::assert_parser!(@finish, $crate::TrailingInput::Reject, $parse, $input);
         assert_eq!(input, $equals)
     }};
+
+    (@finish, $trailing:expr, $parse:ident, $input:expr) => {{
+        let source = $input;
+        $crate::finish_parse(source, $parse(source), $trailing).unwrap_or_else(|e| panic!("{e}"))
+    }};
 }
@@ -0,0 +1,394 @@
+/// Non-negative integer solution of `matrix · x = rhs` with the smallest sum
+///
+/// `upper[j]` bounds `x[j]` from above and keeps the search finite. The
+/// system is brought to reduced row echelon form with exact integer
+/// arithmetic, which writes the sum and every pivot variable as a linear
+/// function of the free variables. Those are then searched branch and bound,
+/// dropping assignments that push a pivot variable out of its bounds or
+/// can't beat the best sum found so far.
+///
+/// # Panics
+///
+/// When the rows of `matrix`, `rhs` and `upper` don't agree on their sizes
+#[must_use]
+pub fn min_sum_solution(matrix: &[Vec<i64>], rhs: &[i64], upper: &[i64]) -> Option<Vec<i64>> {
+    assert_eq!(matrix.len(), rhs.len(), "one right hand side per row");
+    let columns = upper.len();
+    assert!(
+        matrix.iter().all(|row| row.len() == columns),
+        "one upper bound per column"
+    );
+
+    // Identical columns are interchangeable, so they're solved as a single
+    // column bounded by the sum of their bounds and split up afterwards
+    let column = |c: usize| matrix.iter().map(move |row| row[c]);
+    let mut kept: Vec<usize> = Vec::new();
+    let mut duplicates: Vec<Vec<usize>> = Vec::new();
+    let mut kept_upper: Vec<i64> = Vec::new();
+    for (c, &bound) in upper.iter().enumerate() {
+        match kept.iter().position(|&k| column(k).eq(column(c))) {
+            Some(i) => {
+                duplicates[i].push(c);
+                kept_upper[i] = kept_upper[i].saturating_add(bound.max(0));
+            }
+            None => {
+                kept.push(c);
+                duplicates.push(vec![c]);
+                kept_upper.push(bound);
+            }
+        }
+    }
+
+    let mut rows: Vec<Vec<i128>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &rhs)| {
+            kept.iter()
+                .map(|&c| row[c])
+                .chain([rhs])
+                .map(i128::from)
+                .collect()
+        })
+        .collect();
+    let pivots = eliminate(&mut rows, kept.len());
+    if rows[pivots.len()..].iter().any(|row| row[kept.len()] != 0) {
+        return None;
+    }
+    rows.truncate(pivots.len());
+    let mut search = FreeSearch::new(&rows, &pivots, &kept_upper);
+    search.assign(0, 0);
+
+    let (_, values) = search.best?;
+    let mut solution = vec![0; columns];
+    for (group, value) in duplicates.iter().zip(values) {
+        let mut left = i64::try_from(value).expect("values are within their bounds");
+        for &c in group {
+            solution[c] = left.min(upper[c].max(0));
+            left -= solution[c];
+        }
+    }
+    Some(solution)
+}
+
+/// Reduced row echelon form in place, returning the pivot column of each
+/// leading row
+fn eliminate(rows: &mut [Vec<i128>], columns: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for column in 0..columns {
+        let r = pivots.len();
+        let Some(p) = (r..rows.len()).find(|&p| rows[p][column] != 0) else {
+            continue;
+        };
+        rows.swap(r, p);
+        normalize(&mut rows[r]);
+        let pivot_row = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if i == r || factor == 0 {
+                continue;
+            }
+            for (value, &p) in row.iter_mut().zip(&pivot_row) {
+                *value = *value * pivot_row[column] - p * factor;
+            }
+            normalize(row);
+        }
+        pivots.push(column);
+    }
+    pivots
+}
+
+/// Divides a row by the gcd of its entries, making the leading entry positive
+fn normalize(row: &mut [i128]) {
+    let gcd = row.iter().fold(0, |gcd, &v| gcd_of(gcd, v.abs()));
+    let sign = row.iter().find(|&&v| v != 0).map_or(1, |v| v.signum());
+    if gcd > 1 || sign < 0 {
+        row.iter_mut().for_each(|v| *v /= gcd * sign);
+    }
+}
+
+fn gcd_of(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a
+    } else {
+        gcd_of(b, a % b)
+    }
+}
+
+/// Branch and bound over the free variables of a system in reduced row
+/// echelon form
+///
+/// Row `r` reads `pivot_r · x[pivots[r]] + Σ a_rf · x[f] = rhs_r`. Scaled by
+/// the lcm `L` of the pivots the sum of all variables is
+/// `L · Σ x = base + Σ weight_f · x[f]`.
+struct FreeSearch<'a> {
+    rows: &'a [Vec<i128>],
+    pivots: &'a [usize],
+    free: Vec<usize>,
+    upper: &'a [i64],
+    scale: i128,
+    base: i128,
+    weights: Vec<i128>,
+    /// Smallest and largest `Σ a_rf · x[f]` over the free variables from
+    /// index `i` on, as `reach[i][r]`
+    reach: Vec<Vec<(i128, i128)>>,
+    /// Smallest `Σ weight_f · x[f]` over the free variables from index `i` on
+    lowest: Vec<i128>,
+    /// `Σ a_rf · x[f]` over the free variables assigned so far
+    partial: Vec<i128>,
+    values: Vec<i128>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl<'a> FreeSearch<'a> {
+    fn new(rows: &'a [Vec<i128>], pivots: &'a [usize], upper: &'a [i64]) -> Self {
+        let columns = upper.len();
+        let free: Vec<usize> = (0..columns).filter(|c| !pivots.contains(c)).collect();
+        let pivot_of = |r: usize| rows[r][pivots[r]];
+        let scale = (0..rows.len()).fold(1, |l, r| l / gcd_of(l, pivot_of(r)) * pivot_of(r));
+        let base = (0..rows.len())
+            .map(|r| scale / pivot_of(r) * rows[r][columns])
+            .sum();
+        let weights: Vec<i128> = free
+            .iter()
+            .map(|&f| {
+                scale
+                    - (0..rows.len())
+                        .map(|r| scale / pivot_of(r) * rows[r][f])
+                        .sum::<i128>()
+            })
+            .collect();
+
+        let mut reach = vec![vec![(0, 0); rows.len()]; free.len() + 1];
+        let mut lowest = vec![0; free.len() + 1];
+        for (i, &f) in free.iter().enumerate().rev() {
+            let bound = i128::from(upper[f]);
+            for (r, row) in rows.iter().enumerate() {
+                let (lo, hi) = reach[i + 1][r];
+                let contribution = row[f] * bound;
+                reach[i][r] = (lo + contribution.min(0), hi + contribution.max(0));
+            }
+            lowest[i] = lowest[i + 1] + (weights[i] * bound).min(0);
+        }
+
+        Self {
+            rows,
+            pivots,
+            free,
+            upper,
+            scale,
+            base,
+            weights,
+            reach,
+            lowest,
+            partial: vec![0; rows.len()],
+            values: vec![0; columns],
+            best: None,
+        }
+    }
+
+    fn assign(&mut self, i: usize, objective: i128) {
+        let columns = self.values.len();
+        for (r, row) in self.rows.iter().enumerate() {
+            let pivot = row[self.pivots[r]];
+            let rest = row[columns] - self.partial[r];
+            let (lo, hi) = self.reach[i][r];
+            if rest - lo < 0 || rest - hi > i128::from(self.upper[self.pivots[r]]) * pivot {
+                return;
+            }
+        }
+        let bound = self.base + objective + self.lowest[i];
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|&(best, _)| bound >= best * self.scale)
+        {
+            return;
+        }
+        let Some(&column) = self.free.get(i) else {
+            self.solve_pivots();
+            return;
+        };
+
+        let weight = self.weights[i];
+        let bound = i128::from(self.upper[column]);
+        for step in 0..=bound {
+            // Try the values that make the sum smallest first
+            let value = if weight < 0 { bound - step } else { step };
+            for (r, row) in self.rows.iter().enumerate() {
+                self.partial[r] += row[column] * value;
+            }
+            self.values[column] = value;
+            self.assign(i + 1, objective + weight * value);
+            for (r, row) in self.rows.iter().enumerate() {
+                self.partial[r] -= row[column] * value;
+            }
+        }
+        self.values[column] = 0;
+    }
+
+    fn solve_pivots(&mut self) {
+        let columns = self.values.len();
+        for (r, row) in self.rows.iter().enumerate() {
+            let pivot = self.pivots[r];
+            let numerator = row[columns] - self.partial[r];
+            if numerator % row[pivot] != 0 {
+                return;
+            }
+            self.values[pivot] = numerator / row[pivot];
+        }
+        let sum = self.values.iter().sum();
+        if self.best.as_ref().is_none_or(|&(best, _)| sum < best) {
+            self.best = Some((sum, self.values.clone()));
+        }
+    }
+}
+
+/// Columns to add over GF(2) to get `target` with as few columns as possible
+///
+/// Every column and `target` are bitmasks of up to 64 rows, and the result
+/// has bit `j` set when `columns[j]` is used. Gaussian elimination finds one
+/// solution, then every combination of the nullspace basis is tried, so this
+/// takes `2^(columns - rank)` steps.
+///
+/// # Panics
+///
+/// When there are more than 64 columns
+#[must_use]
+pub fn min_weight_gf2(columns: &[u64], target: u64) -> Option<u64> {
+    assert!(columns.len() <= 64, "at most 64 columns fit a mask");
+    // One equation per row of the columns: which columns touch it, and its target bit
+    let mut rows: Vec<(u64, bool)> = (0..64)
+        .map(|row| {
+            let mask = columns
+                .iter()
+                .enumerate()
+                .filter(|&(_, column)| column >> row & 1 == 1)
+                .fold(0, |mask, (j, _)| mask | 1 << j);
+            (mask, target >> row & 1 == 1)
+        })
+        .filter(|&(mask, bit)| mask != 0 || bit)
+        .collect();
+
+    let mut pivots = Vec::new();
+    for column in 0..columns.len() {
+        let r = pivots.len();
+        let Some(p) = (r..rows.len()).find(|&p| rows[p].0 >> column & 1 == 1) else {
+            continue;
+        };
+        rows.swap(r, p);
+        let pivot = rows[r];
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != r && row.0 >> column & 1 == 1 {
+                row.0 ^= pivot.0;
+                row.1 ^= pivot.1;
+            }
+        }
+        pivots.push(column);
+    }
+    if rows[pivots.len()..].iter().any(|&(_, bit)| bit) {
+        return None;
+    }
+
+    // Free columns set to zero, every pivot column takes its row's target bit
+    let particular = rows
+        .iter()
+        .zip(&pivots)
+        .filter(|((_, bit), _)| *bit)
+        .fold(0u64, |solution, (_, &pivot)| solution | 1 << pivot);
+    // Setting one free column forces the pivots of the rows it appears in
+    let nullspace: Vec<u64> = (0..columns.len())
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            rows.iter()
+                .zip(&pivots)
+                .filter(|((mask, _), _)| mask >> free & 1 == 1)
+                .fold(1 << free, |vector, (_, &pivot)| vector | 1 << pivot)
+        })
+        .collect();
+
+    // Walk every combination of the basis in Gray code order, one xor per step
+    let mut solution = particular;
+    let mut best = particular;
+    for step in 1u64..1 << nullspace.len() {
+        solution ^= nullspace[step.trailing_zeros() as usize];
+        if solution.count_ones() < best.count_ones() {
+            best = solution;
+        }
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique() {
+        // x + y = 5, x - y = 1
+        let solution = min_sum_solution(&[vec![1, 1], vec![1, -1]], &[5, 1], &[5, 5]);
+        assert_eq!(solution, Some(vec![3, 2]));
+        assert_eq!(
+            min_sum_solution(&[vec![2, 0]], &[3], &[5, 5]),
+            None,
+            "no integer solution"
+        );
+        assert_eq!(
+            min_sum_solution(&[vec![1, 1], vec![1, 1]], &[2, 3], &[5, 5]),
+            None,
+            "inconsistent"
+        );
+    }
+
+    #[test]
+    fn test_free_variables() {
+        // Counters {3,5,4,7} and buttons (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let target = [3, 5, 4, 7];
+        let matrix: Vec<Vec<i64>> = (0..4)
+            .map(|i| buttons.iter().map(|b| i64::from(b.contains(&i))).collect())
+            .collect();
+        let solution = min_sum_solution(&matrix, &target, &[7; 6]).unwrap();
+        assert_eq!(solution.iter().sum::<i64>(), 10);
+        for (row, target) in matrix.iter().zip(target) {
+            let total: i64 = row.iter().zip(&solution).map(|(a, x)| a * x).sum();
+            assert_eq!(total, target);
+        }
+        assert!(solution.iter().all(|&x| x >= 0));
+    }
+
+    #[test]
+    fn test_identical_columns() {
+        // Merged columns share the sum of their bounds, and each gets its own
+        // share back within its bound
+        assert_eq!(
+            min_sum_solution(&[vec![1, 1]], &[2], &[1, 1]),
+            Some(vec![1, 1])
+        );
+        assert_eq!(
+            min_sum_solution(&[vec![1, 1]], &[3], &[1, 5]),
+            Some(vec![1, 2])
+        );
+        assert_eq!(min_sum_solution(&[vec![1, 1]], &[7], &[1, 5]), None);
+    }
+
+    #[test]
+    fn test_gf2() {
+        // Lights .##. and buttons (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let buttons = [0b1000, 0b1010, 0b0100, 0b1100, 0b0101, 0b0011];
+        let presses = min_weight_gf2(&buttons, 0b0110).unwrap();
+        assert_eq!(presses.count_ones(), 2);
+        let lights = buttons
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| presses >> j & 1 == 1)
+            .fold(0, |lights, (_, button)| lights ^ button);
+        assert_eq!(lights, 0b0110);
+
+        assert_eq!(min_weight_gf2(&buttons, 0), Some(0));
+        assert_eq!(min_weight_gf2(&[0b01, 0b01], 0b10), None);
+        assert_eq!(
+            min_weight_gf2(&[0b011, 0b110, 0b101, 0b111], 0b111),
+            Some(0b1000)
+        );
+    }
+}
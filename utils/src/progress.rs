@@ -0,0 +1,162 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Whether the current run prints text a person is watching
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns progress reports on for runs with text output
+///
+/// They stay off anyway when stdout or stderr isn't a terminal, so piped and
+/// machine-readable output is never mixed with progress lines.
+pub(crate) fn enable(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+const REDRAW: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+/// A progress line on stderr for long loops, showing a bar and the rate
+///
+/// Steps can be counted from any thread, so a `Progress` can be shared by a
+/// `rayon` loop:
+///
+/// ```
+/// # use utils::Progress;
+/// let progress = Progress::new("machines", 3);
+/// let total: u32 = [1, 2, 3].iter().inspect(|_| progress.tick()).sum();
+/// ```
+///
+/// The line is redrawn at most every 100ms and cleared when the `Progress`
+/// is dropped.
+#[derive(Debug)]
+pub struct Progress {
+    label: String,
+    total: Option<usize>,
+    done: AtomicUsize,
+    start: Instant,
+    last_draw: Mutex<Option<Instant>>,
+    visible: bool,
+}
+
+impl Progress {
+    /// Progress through `total` steps
+    #[must_use]
+    pub fn new(label: impl Into<String>, total: usize) -> Self {
+        Self::with_total(label.into(), Some(total))
+    }
+
+    /// Progress through an unknown number of steps, only the rate is shown
+    #[must_use]
+    pub fn unbounded(label: impl Into<String>) -> Self {
+        Self::with_total(label.into(), None)
+    }
+
+    fn with_total(label: String, total: Option<usize>) -> Self {
+        Self {
+            label,
+            total,
+            done: AtomicUsize::new(0),
+            start: Instant::now(),
+            last_draw: Mutex::new(None),
+            visible: ENABLED.load(Ordering::Relaxed)
+                && io::stdout().is_terminal()
+                && io::stderr().is_terminal(),
+        }
+    }
+
+    /// Counts one finished step
+    pub fn tick(&self) {
+        self.advance(1);
+    }
+
+    /// Counts `steps` finished steps
+    pub fn advance(&self, steps: usize) {
+        let done = self.done.fetch_add(steps, Ordering::Relaxed) + steps;
+        if !self.visible {
+            return;
+        }
+        // Whoever holds the lock is drawing already, no need to wait for it
+        let Ok(mut last_draw) = self.last_draw.try_lock() else {
+            return;
+        };
+        let now = Instant::now();
+        if last_draw.is_none_or(|last| now - last >= REDRAW) || Some(done) == self.total {
+            *last_draw = Some(now);
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K{}", self.line(done, now - self.start));
+            let _ = stderr.flush();
+        }
+    }
+
+    /// Steps finished so far
+    #[must_use]
+    pub fn done(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    fn line(&self, done: usize, elapsed: Duration) -> String {
+        let rate = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        match self.total {
+            Some(total) => {
+                let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+                let filled = filled.min(BAR_WIDTH);
+                format!(
+                    "{} [{}{}] {done}/{total} {rate:.1}/s",
+                    self.label,
+                    "#".repeat(filled),
+                    " ".repeat(BAR_WIDTH - filled)
+                )
+            }
+            None => format!("{} {done} {rate:.1}/s", self.label),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let drawn = self.last_draw.get_mut().is_ok_and(|last| last.is_some());
+        if drawn {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        let progress = Progress::new("machines", 4);
+        assert_eq!(
+            progress.line(1, Duration::from_secs(2)),
+            format!("machines [{}{}] 1/4 0.5/s", "#".repeat(7), " ".repeat(23))
+        );
+        assert_eq!(
+            Progress::unbounded("states").line(30, Duration::from_secs(10)),
+            "states 30 3.0/s"
+        );
+        assert_eq!(
+            Progress::new("empty", 0).line(0, Duration::from_secs(1)),
+            format!("empty [{}] 0/0 0.0/s", "#".repeat(30))
+        );
+    }
+
+    #[test]
+    fn test_threads() {
+        let progress = Progress::new("work", 400);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| (0..100).for_each(|_| progress.tick()));
+            }
+        });
+        assert_eq!(progress.done(), 400);
+        assert!(!progress.visible, "tests never draw");
+    }
+}
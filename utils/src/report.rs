@@ -0,0 +1,275 @@
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::{Answer, AnswerKey, AocResult, SolutionPart, Timings};
+
+/// How the answers of a day are printed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// `Part 1: ...` lines for humans
+    #[default]
+    Text,
+    /// A single JSON object for scripts
+    Json,
+}
+
+fn part_number(part: SolutionPart) -> u8 {
+    match part {
+        SolutionPart::PartOne => 1,
+        SolutionPart::PartTwo => 2,
+        SolutionPart::Both => unreachable!("an answer belongs to a single part"),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PartReport {
+    part: u8,
+    answer: Answer,
+    type_name: &'static str,
+    expected: Option<Answer>,
+}
+
+impl PartReport {
+    /// `None` until the answer was verified or when no answer is known
+    fn verified(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| *expected == self.answer)
+    }
+}
+
+/// Collects answers and timings of a run and prints them in the chosen format
+#[derive(Debug)]
+pub struct Report {
+    day: u8,
+    input: Option<String>,
+    format: OutputFormat,
+    show_timings: bool,
+    parts: Vec<PartReport>,
+    timings: Timings,
+    verify: bool,
+}
+
+impl Report {
+    /// Creates an empty report for `day`
+    #[must_use]
+    pub fn new(day: u8, format: OutputFormat, show_timings: bool) -> Self {
+        Self {
+            day,
+            input: None,
+            format,
+            show_timings,
+            parts: Vec::new(),
+            timings: Timings::default(),
+            verify: false,
+        }
+    }
+
+    /// Marks the report as one of several inputs
+    ///
+    /// Nothing is printed while it runs, answers end up in the batch summary.
+    #[must_use]
+    pub fn for_input(mut self, path: &Path) -> Self {
+        self.input = Some(path.display().to_string());
+        self
+    }
+
+    /// Input the report belongs to in batch mode
+    #[must_use]
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    /// Recorded answer of a part
+    #[must_use]
+    pub fn answer_of(&self, part: SolutionPart) -> Option<&Answer> {
+        let part = part_number(part);
+        self.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map(|p| &p.answer)
+    }
+
+    /// Time spent in each phase so far
+    #[must_use]
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Runs `f` and records how long it took under `phase`
+    pub fn time<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        self.timings.time(phase, f)
+    }
+
+    /// Records the answer of a part
+    ///
+    /// Text output is printed right away so long running days show
+    /// part 1 before part 2 is done.
+    pub fn answer<T: Into<Answer>>(&mut self, part: SolutionPart, answer: T) {
+        let part = part_number(part);
+        let answer = answer.into();
+        if self.format == OutputFormat::Text && self.input.is_none() {
+            println!("Part {part}: {answer}");
+        }
+        self.parts.push(PartReport {
+            part,
+            answer,
+            type_name: std::any::type_name::<T>(),
+            expected: None,
+        });
+    }
+
+    /// Checks the recorded answers against `key`
+    pub fn verify(&mut self, key: &AnswerKey) {
+        self.verify = true;
+        for part in &mut self.parts {
+            let solution_part = if part.part == 1 {
+                SolutionPart::PartOne
+            } else {
+                SolutionPart::PartTwo
+            };
+            part.expected = key.get(solution_part).cloned();
+        }
+    }
+
+    /// Prints everything that was not printed while running
+    ///
+    /// # Errors
+    ///
+    /// When a verified answer doesn't match the answer key
+    pub fn finish(&self) -> AocResult<()> {
+        match self.format {
+            OutputFormat::Text => {
+                if self.verify {
+                    println!();
+                    for line in self.verification() {
+                        println!("{line}");
+                    }
+                }
+                if self.show_timings {
+                    println!();
+                    println!("{}", self.timings);
+                }
+            }
+            OutputFormat::Json => println!("{}", self.to_json()),
+        }
+        let failed = self
+            .parts
+            .iter()
+            .filter(|p| p.verified() == Some(false))
+            .map(|p| p.part.to_string())
+            .collect::<Vec<_>>();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("wrong answer for part {}", failed.join(" and "))
+        }
+    }
+
+    /// One line per part telling whether it matched the answer key
+    fn verification(&self) -> impl Iterator<Item = String> + '_ {
+        self.parts
+            .iter()
+            .map(|part| match (part.verified(), &part.expected) {
+                (Some(true), _) => format!("Part {}: pass", part.part),
+                (Some(false), Some(expected)) => {
+                    format!("Part {}: FAIL (expected {expected})", part.part)
+                }
+                _ => format!("Part {}: no known answer", part.part),
+            })
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let parts = self
+            .parts
+            .iter()
+            .map(|p| {
+                let mut part = json!({ "part": p.part, "answer": p.answer, "type": p.type_name });
+                if self.verify {
+                    part["verified"] = json!(p.verified());
+                }
+                part
+            })
+            .collect::<Vec<_>>();
+        let timings = self
+            .timings
+            .phases()
+            .map(|(phase, elapsed)| json!({ "phase": phase, "elapsed_ns": elapsed.as_nanos() }))
+            .collect::<Vec<_>>();
+        let mut json = json!({
+            "day": self.day,
+            "parts": parts,
+            "timings": timings,
+            "total_ns": self.timings.total().as_nanos(),
+        });
+        if let Some(input) = &self.input {
+            json["input"] = json!(input);
+        }
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A report of day 5 with both parts answered, only the first one right
+    fn report(format: OutputFormat) -> Report {
+        let mut report = Report::new(5, format, true);
+        report.time("Parse", || ());
+        report.answer(SolutionPart::PartOne, 3usize);
+        report.answer(SolutionPart::PartTwo, 15u64);
+        report.verify(&"part1 = 3\npart2 = 14\n".parse().unwrap());
+        report
+    }
+
+    #[test]
+    fn test_json() {
+        let mut json = report(OutputFormat::Json).to_json();
+        let total = json["total_ns"].take();
+        let elapsed = json["timings"][0]["elapsed_ns"].take();
+        assert_eq!(total, elapsed);
+        assert!(total.is_u64());
+        assert_eq!(
+            json,
+            json!({
+                "day": 5,
+                "parts": [
+                    { "part": 1, "answer": 3, "type": "usize", "verified": true },
+                    { "part": 2, "answer": 15, "type": "u64", "verified": false },
+                ],
+                "timings": [{ "phase": "Parse", "elapsed_ns": null }],
+                "total_ns": null,
+            })
+        );
+        assert!(report(OutputFormat::Json).finish().is_err());
+    }
+
+    #[test]
+    fn test_json_unverified() {
+        let mut report = Report::new(1, OutputFormat::Json, false).for_input(Path::new("a.txt"));
+        report.answer(SolutionPart::PartOne, String::from("EFHL"));
+        let json = report.to_json();
+        assert_eq!(json["input"], "a.txt");
+        assert_eq!(
+            json["parts"],
+            json!([{ "part": 1, "answer": "EFHL", "type": "alloc::string::String" }])
+        );
+        assert!(report.finish().is_ok());
+    }
+
+    #[test]
+    fn test_verification() {
+        let mut report = report(OutputFormat::Json);
+        assert_eq!(
+            report.verification().collect::<Vec<_>>(),
+            ["Part 1: pass", "Part 2: FAIL (expected 14)"]
+        );
+        report.verify(&AnswerKey::default());
+        assert_eq!(
+            report.verification().collect::<Vec<_>>(),
+            ["Part 1: no known answer", "Part 2: no known answer"]
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Every node a search reached, with the cheapest way it found to get there
+///
+/// Each node is visited once, so cycles and states reachable in several ways
+/// don't blow up the search.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    nodes: Vec<Visit<N, C>>,
+    index: HashMap<N, usize>,
+    goal: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Visit<N, C> {
+    node: N,
+    parent: Option<usize>,
+    cost: C,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![Visit {
+                node: start,
+                parent: None,
+                cost,
+            }],
+            goal: None,
+        }
+    }
+
+    /// Goal the search stopped at, `None` when no goal was reachable
+    #[must_use]
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.map(|i| &self.nodes[i].node)
+    }
+
+    /// Cost of reaching the goal
+    #[must_use]
+    pub fn cost(&self) -> Option<C> {
+        self.goal.map(|i| self.nodes[i].cost)
+    }
+
+    /// Cost of the cheapest path found to `node`
+    #[must_use]
+    pub fn cost_to(&self, node: &N) -> Option<C> {
+        self.index.get(node).map(|&i| self.nodes[i].cost)
+    }
+
+    /// Nodes from the start to the goal, both included
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.map(|i| self.path_from(i))
+    }
+
+    /// Nodes from the start to `node`, both included
+    #[must_use]
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.index.get(node).map(|&i| self.path_from(i))
+    }
+
+    /// Every node reached with the cost of getting there
+    pub fn reached(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|visit| (&visit.node, visit.cost))
+    }
+
+    fn path_from(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].node.clone()];
+        while let Some(parent) = self.nodes[i].parent {
+            path.push(self.nodes[parent].node.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Records reaching `node` from `parent` for `cost`, returning its index
+    /// when that's the cheapest way found so far
+    fn reach(&mut self, node: N, parent: usize, cost: C) -> Option<usize>
+    where
+        C: Ord,
+    {
+        match self.index.entry(node) {
+            Entry::Occupied(entry) => {
+                let visit = &mut self.nodes[*entry.get()];
+                (cost < visit.cost).then(|| {
+                    visit.parent = Some(parent);
+                    visit.cost = cost;
+                    *entry.get()
+                })
+            }
+            Entry::Vacant(entry) => {
+                let i = self.nodes.len();
+                self.nodes.push(Visit {
+                    node: entry.key().clone(),
+                    parent: Some(parent),
+                    cost,
+                });
+                entry.insert(i);
+                Some(i)
+            }
+        }
+    }
+}
+
+/// Breadth-first search from `start`, every step costing one
+///
+/// Nodes are checked with `is_goal` as soon as they're discovered; pass
+/// `|_| false` to explore everything reachable.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start, 0);
+    if is_goal(&search.nodes[0].node) {
+        search.goal = Some(0);
+        return search;
+    }
+    let mut current = 0;
+    while current < search.nodes.len() {
+        let node = search.nodes[current].node.clone();
+        let cost = search.nodes[current].cost + 1;
+        for next in successors(&node) {
+            if let Some(i) = search.reach(next, current, cost) {
+                if is_goal(&search.nodes[i].node) {
+                    search.goal = Some(i);
+                    return search;
+                }
+            }
+        }
+        current += 1;
+    }
+    search
+}
+
+/// Cheapest path search from `start`, successors come with the cost of the step
+///
+/// Nodes are checked with `is_goal` when they're the cheapest left to
+/// expand; pass `|_| false` to find the cost of everything reachable.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Cheapest path search from `start` guided by `heuristic`
+///
+/// `heuristic` estimates the cost left to a goal and must never overestimate
+/// it, otherwise the path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start, C::default());
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&search.nodes[0].node), 0))]);
+    let mut expanded = vec![false];
+    while let Some(Reverse((_, current))) = queue.pop() {
+        if std::mem::replace(&mut expanded[current], true) {
+            continue;
+        }
+        let node = search.nodes[current].node.clone();
+        if is_goal(&node) {
+            search.goal = Some(current);
+            break;
+        }
+        let cost = search.nodes[current].cost;
+        for (next, step) in successors(&node) {
+            if let Some(i) = search.reach(next, current, cost + step) {
+                expanded.resize(search.nodes.len(), false);
+                expanded[i] = false;
+                let estimate = search.nodes[i].cost + heuristic(&search.nodes[i].node);
+                queue.push(Reverse((estimate, i)));
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+...#...";
+
+    fn maze() -> Grid<bool> {
+        Grid::from_rows(
+            MAZE.lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let open = |pos: &Pos| {
+            maze.neighbors4(*pos)
+                .filter(|&p| !maze[p])
+                .collect::<Vec<_>>()
+        };
+        let search = bfs((0, 0), open, |&pos| pos == (0, 6));
+        assert_eq!(search.cost(), Some(12));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), ((0, 0), (0, 6)));
+        assert!(path
+            .windows(2)
+            .all(|w| maze.neighbors4(w[0]).any(|p| p == w[1])));
+
+        let everything = bfs((0, 0), open, |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(everything.reached().count(), 26);
+        assert_eq!(everything.cost_to(&(4, 6)), Some(10));
+        assert_eq!(bfs((0, 0), open, |&pos| pos == (0, 2)).cost(), None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // Moving right is cheap, everything else costs ten
+        let maze = maze();
+        let moves = |&(row, col): &Pos| {
+            maze.neighbors4((row, col))
+                .filter(|&p| !maze[p])
+                .map(|p| (p, if p.1 > col { 1 } else { 10 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 6);
+        let cheapest = dijkstra((0, 0), moves, |&pos| pos == goal);
+        assert_eq!(cheapest.cost(), Some(6 + 4 * 10));
+        let guided = astar(
+            (0, 0),
+            moves,
+            |&(row, col)| (goal.0 - row) * 10 + (goal.1 - col),
+            |&pos| pos == goal,
+        );
+        assert_eq!(guided.cost(), cheapest.cost());
+        assert_eq!(guided.path().unwrap().last(), Some(&goal));
+        assert!(guided.reached().count() <= cheapest.reached().count());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use crate::{finish_parse, Answer, AocResult, ParseResult, Report, SolutionPart, TrailingInput};
+
+/// A parser for the input of a day
+pub type ParseFn<T> = for<'a> fn(&'a str) -> ParseResult<'a, T>;
+
+/// A day's puzzle: how to read the input and solve both parts
+pub trait Solution {
+    /// Day of December the puzzle was released
+    const DAY: u8;
+
+    /// What to do with input the parser leaves behind
+    const TRAILING_INPUT: TrailingInput = TrailingInput::Reject;
+
+    /// Parser for part 2 when it reads the input differently than part 1
+    ///
+    /// `None` reuses the input parsed by [`Solution::parse`].
+    const PARSE_PART2: Option<ParseFn<Self::Input>> = None;
+
+    /// The parsed input both parts work on
+    type Input;
+
+    /// Answer of part 1
+    type Part1: Into<Answer>;
+
+    /// Answer of part 2
+    type Part2: Into<Answer>;
+
+    /// Parses the puzzle input
+    ///
+    /// # Errors
+    ///
+    /// When the input doesn't match the puzzle format
+    fn parse(input: &str) -> ParseResult<'_, Self::Input>;
+
+    /// Solves part 1
+    ///
+    /// # Errors
+    ///
+    /// When the input has no solution
+    fn part1(input: &Self::Input) -> AocResult<Self::Part1>;
+
+    /// Solves part 2
+    ///
+    /// # Errors
+    ///
+    /// When the input has no solution
+    fn part2(input: &Self::Input) -> AocResult<Self::Part2>;
+}
+
+/// Parses `input` and solves the requested parts, recording timings and
+/// answers in `report`
+///
+/// # Errors
+///
+/// When parsing or one of the parts fails
+pub fn solve<S: Solution>(input: &str, part: SolutionPart, report: &mut Report) -> AocResult<()> {
+    solve_with::<S, _, _>(input, part, report, S::part1, S::part2)
+}
+
+/// Like [`solve`], with the parts given separately so a day's binary can
+/// pass them options from its own command line
+///
+/// # Errors
+///
+/// When parsing or one of the parts fails
+pub fn solve_with<S: Solution, P1: Into<Answer>, P2: Into<Answer>>(
+    input: &str,
+    part: SolutionPart,
+    report: &mut Report,
+    part1: impl Fn(&S::Input) -> AocResult<P1>,
+    part2: impl Fn(&S::Input) -> AocResult<P2>,
+) -> AocResult<()> {
+    let run = |p| part == SolutionPart::Both || part == p;
+    let parse = |report: &mut Report, phase, parse: ParseFn<S::Input>| {
+        let parsed = report.time(phase, || parse(input));
+        finish_parse(input, parsed, S::TRAILING_INPUT)
+    };
+    match S::PARSE_PART2 {
+        None => {
+            let parsed = parse(report, "Parse", S::parse)?;
+            if run(SolutionPart::PartOne) {
+                let answer = report.time("Part 1", || part1(&parsed))?;
+                report.answer(SolutionPart::PartOne, answer);
+            }
+            if run(SolutionPart::PartTwo) {
+                let answer = report.time("Part 2", || part2(&parsed))?;
+                report.answer(SolutionPart::PartTwo, answer);
+            }
+        }
+        Some(parse2) => {
+            if run(SolutionPart::PartOne) {
+                let parsed = parse(report, "Parse 1", S::parse)?;
+                let answer = report.time("Part 1", || part1(&parsed))?;
+                report.answer(SolutionPart::PartOne, answer);
+            }
+            if run(SolutionPart::PartTwo) {
+                let parsed = parse(report, "Parse 2", parse2)?;
+                let answer = report.time("Part 2", || part2(&parsed))?;
+                report.answer(SolutionPart::PartTwo, answer);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A [`Solution`] with its types erased, as stored in the [`Registry`]
+pub type SolveFn = fn(&str, SolutionPart, &mut Report) -> AocResult<()>;
+
+/// Every known day, so they can be run from a single binary
+#[derive(Debug, Default, Clone)]
+pub struct Registry {
+    days: BTreeMap<u8, SolveFn>,
+}
+
+impl Registry {
+    /// Adds a day, replacing an earlier solution for the same day
+    #[must_use]
+    pub fn with<S: Solution>(mut self) -> Self {
+        self.days.insert(S::DAY, solve::<S>);
+        self
+    }
+
+    /// Registered days in order
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.days.keys().copied()
+    }
+
+    /// Solver for `day`, if it was registered
+    #[must_use]
+    pub fn get(&self, day: u8) -> Option<SolveFn> {
+        self.days.get(&day).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputFormat;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 1;
+
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> ParseResult<'_, usize> {
+            Ok(("", input.lines().count()))
+        }
+
+        fn part1(input: &usize) -> AocResult<usize> {
+            Ok(*input)
+        }
+
+        fn part2(input: &usize) -> AocResult<usize> {
+            Ok(input * 2)
+        }
+    }
+
+    /// Reads the input as its length in bytes for part 2
+    struct Bytes;
+
+    impl Solution for Bytes {
+        const DAY: u8 = 2;
+        const PARSE_PART2: Option<ParseFn<usize>> = Some(|input| Ok(("", input.len())));
+
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> ParseResult<'_, usize> {
+            Lines::parse(input)
+        }
+
+        fn part1(input: &usize) -> AocResult<usize> {
+            Lines::part1(input)
+        }
+
+        fn part2(input: &usize) -> AocResult<usize> {
+            Lines::part2(input)
+        }
+    }
+
+    fn phases<S: Solution>(part: SolutionPart) -> (Vec<&'static str>, Report) {
+        let mut report = Report::new(S::DAY, OutputFormat::Text, true);
+        solve::<S>("ab\ncd\n", part, &mut report).unwrap();
+        let phases = report.timings().phases().map(|(phase, _)| phase).collect();
+        (phases, report)
+    }
+
+    #[test]
+    fn test_phases() {
+        let (names, report) = phases::<Lines>(SolutionPart::Both);
+        assert_eq!(names, ["Parse", "Part 1", "Part 2"]);
+        assert_eq!(
+            report.answer_of(SolutionPart::PartTwo),
+            Some(&4usize.into())
+        );
+
+        let (names, report) = phases::<Bytes>(SolutionPart::Both);
+        assert_eq!(names, ["Parse 1", "Part 1", "Parse 2", "Part 2"]);
+        assert_eq!(
+            report.answer_of(SolutionPart::PartOne),
+            Some(&2usize.into())
+        );
+        assert_eq!(
+            report.answer_of(SolutionPart::PartTwo),
+            Some(&12usize.into())
+        );
+
+        let (names, _) = phases::<Bytes>(SolutionPart::PartTwo);
+        assert_eq!(names, ["Parse 2", "Part 2"]);
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// Wall-clock time spent in each phase of a solution
+#[derive(Debug, Default, Clone)]
+pub struct Timings {
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    /// Runs `f` and records how long it took under `phase`
+    pub fn time<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.phases.push((phase, start.elapsed()));
+        result
+    }
+
+    /// Recorded phases in the order they ran
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, Duration)> + '_ {
+        self.phases.iter().copied()
+    }
+
+    /// Sum of all recorded phases
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, d)| *d).sum()
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .phases
+            .iter()
+            .map(|(phase, _)| phase.len())
+            .chain(["Total".len()])
+            .max()
+            .unwrap_or_default();
+        for (phase, duration) in self.phases() {
+            writeln!(f, "{phase:<width$}  {duration:>12.3?}")?;
+        }
+        write!(f, "{:<width$}  {:>12.3?}", "Total", self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut timings = Timings::default();
+        assert_eq!(timings.time("Parse", || 2), 2);
+        timings.time("Part 1", || ());
+        assert_eq!(
+            timings.phases().map(|(phase, _)| phase).collect::<Vec<_>>(),
+            ["Parse", "Part 1"]
+        );
+        assert_eq!(timings.total(), timings.phases().map(|(_, d)| d).sum());
+
+        let text = timings.to_string();
+        let names: Vec<_> = text.lines().map(|line| line[..6].trim_end()).collect();
+        assert_eq!(names, ["Parse", "Part 1", "Total"]);
+        assert!(text.lines().all(|line| line.chars().count() == 6 + 2 + 12));
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Context;
 use cargo_metadata::Metadata;
-use indoc::{formatdoc, indoc};
+use indoc::formatdoc;
 use log::debug;
 use reqwest::blocking as req;
 use std::{
@@ -52,61 +52,88 @@ utils = {{ path = "../utils", version = "*" }}
     } else {
         println!("Cargo.toml exists");
     }
-    if let Ok(mut file) = create_new(location.join("src").join("main.rs")) {
-        println!("Creating main.rs");
+    if let Ok(mut file) = create_new(location.join("src").join("lib.rs")) {
+        println!("Creating lib.rs");
         file.write_all(
-            indoc! { r#"
+            formatdoc! { r#"
 use itertools::Itertools;
 #[allow(clippy::wildcard_imports)]
 use utils::*;
 
 #[derive(Debug, PartialEq)]
-struct InputData {}
+pub struct InputData {{}}
 
-fn parse(input: &str) -> ParseResult<'_, InputData> {
-    use nom::{
+fn parse(input: &str) -> ParseResult<'_, InputData> {{
+    use nom::{{
         Parser
-    };
+    }};
 
     todo!()
-}
+}}
 
 #[allow(clippy::unnecessary_wraps)]
-fn part1(input: &InputData) -> AocResult<()> {
+fn part1(input: &InputData) -> AocResult<()> {{
     Ok(())
-}
+}}
 
 #[allow(clippy::unnecessary_wraps)]
-fn part2(input: &InputData) -> AocResult<()> {
+fn part2(input: &InputData) -> AocResult<()> {{
     Ok(())
-}
+}}
+
+pub struct Day{day:0>2};
+
+impl Solution for Day{day:0>2} {{
+    const DAY: u8 = {day};
+
+    type Input = InputData;
+    type Part1 = ();
+    type Part2 = ();
+
+    fn parse(input: &str) -> ParseResult<'_, InputData> {{
+        parse(input)
+    }}
 
-aoc_main!(parse, part1, part2);
+    fn part1(input: &InputData) -> AocResult<()> {{
+        part1(input)
+    }}
+
+    fn part2(input: &InputData) -> AocResult<()> {{
+        part2(input)
+    }}
+}}
 
 #[cfg(test)]
-mod tests {
+mod tests {{
     use super::*;
 
     const INPUT: &str = "";
 
     #[test]
-    fn test_parser() {
-        assert_parser!(parse, INPUT, InputData {});
-    }
+    fn test_parser() {{
+        assert_parser!(parse, INPUT, InputData {{}});
+    }}
 
     #[test]
-    fn test_part1() {
+    fn test_part1() {{
         // assert_part!(parse, part1, INPUT, ());
-    }
+    }}
 
     #[test]
-    fn test_part2() {
+    fn test_part2() {{
         // assert_part!(parse, part2, INPUT, ());
-    }
-}
+    }}
+}}
 "# }
             .as_bytes(),
         )?;
+    } else {
+        println!("lib.rs exists");
+    }
+    if let Ok(mut file) = create_new(location.join("src").join("main.rs")) {
+        println!("Creating main.rs");
+        file.write_all(format!("utils::aoc_main!(day_{day:0>2}::Day{day:0>2});\n").as_bytes())?;
+        println!("Register day_{day:0>2}::Day{day:0>2} in aoc/src/main.rs to run it with `aoc`");
     } else {
         println!("main.rs exists");
     }